@@ -0,0 +1,102 @@
+// Embed the static assets under content-hashed paths, so they can be cached forever, and
+// precompress the text ones with gzip and brotli.
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+// Files in src/browser, their public path and content type.
+const ASSETS: &[(&str, &str, &str)] = &[
+    ("images/favicon.png", "/favicon.png", "image/png"),
+    ("images/card.png", "/card.png", "image/png"),
+    ("images/yourdle.svg", "/yourdle.svg", "image/svg+xml"),
+    ("images/info.svg", "/info.svg", "image/svg+xml"),
+    ("images/contrast.svg", "/contrast.svg", "image/svg+xml"),
+    ("images/stats.svg", "/stats.svg", "image/svg+xml"),
+    ("style.css", "/style.css", "text/css; charset=utf-8"),
+    ("noscript.css", "/noscript.css", "text/css; charset=utf-8"),
+    (
+        "script.js",
+        "/script.js",
+        "application/javascript; charset=utf-8",
+    ),
+    ("new.js", "/new.js", "application/javascript; charset=utf-8"),
+];
+
+fn main() {
+    let browser_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/browser");
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut consts = String::new();
+    let mut assets = String::from("pub static ASSETS: &[Asset] = &[\n");
+    for (file, path, content_type) in ASSETS {
+        let file = browser_dir.join(file);
+        println!("cargo:rerun-if-changed={}", file.display());
+        let body = fs::read(&file).unwrap();
+        let hash: String = Sha256::digest(&body)
+            .iter()
+            .take(5)
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        // e.g. /style.css -> /style.0123456789.css
+        let (stem, ext) = path.rsplit_once('.').unwrap();
+        let hashed_path = format!("{}.{}.{}", stem, hash, ext);
+        // e.g. /style.css -> STYLE_CSS
+        let name = path[1..].replace('.', "_").to_uppercase();
+        writeln!(consts, "pub const {}: &str = {:?};", name, hashed_path).unwrap();
+        // PNGs are already compressed.
+        let (gzip, brotli) = match content_type.starts_with("image/png") {
+            true => (None, None),
+            false => (
+                compressed(&out_dir, &format!("{}.gz", hash), gzip(&body), &body),
+                compressed(&out_dir, &format!("{}.br", hash), brotli(&body), &body),
+            ),
+        };
+        writeln!(
+            assets,
+            "    Asset {{ path: {:?}, hashed_path: {:?}, hash: {:?}, content_type: {:?}, body: include_bytes!({:?}), gzip: {}, brotli: {} }},",
+            path,
+            hashed_path,
+            hash,
+            content_type,
+            file.display().to_string(),
+            gzip.unwrap_or("None".to_owned()),
+            brotli.unwrap_or("None".to_owned()),
+        )
+        .unwrap();
+    }
+    assets.push_str("];\n");
+    let out = Path::new(&out_dir).join("assets.rs");
+    fs::write(out, consts + &assets).unwrap();
+}
+
+fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(body).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn brotli(body: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::new();
+    {
+        let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+        encoder.write_all(body).unwrap();
+    }
+    compressed
+}
+
+// Write a compressed variant to OUT_DIR, if it's worth it, and return the code to embed it.
+fn compressed(out_dir: &str, name: &str, compressed: Vec<u8>, body: &[u8]) -> Option<String> {
+    if compressed.len() >= body.len() {
+        return None;
+    }
+    let file = Path::new(out_dir).join(name);
+    fs::write(&file, compressed).unwrap();
+    Some(format!(
+        "Some(include_bytes!({:?}))",
+        file.display().to_string()
+    ))
+}
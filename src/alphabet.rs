@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Letters with a separate form at the end of a word, and the form they take elsewhere. Arabic
+// letters change shape in place, so only Hebrew and Greek need folding.
+const FINAL_FORMS: [(char, char); 6] = [
+    ('ך', 'כ'),
+    ('ם', 'מ'),
+    ('ן', 'נ'),
+    ('ף', 'פ'),
+    ('ץ', 'צ'),
+    ('ς', 'σ'),
+];
+
+// The direction a game's words are written in.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+
+    // Right-to-left if the word list is written in Hebrew or Arabic.
+    pub fn detect(words: &[String]) -> Direction {
+        let rtl = words.iter().flat_map(|word| word.chars()).any(|c| {
+            ('\u{0590}'..='\u{05FF}').contains(&c) || ('\u{0600}'..='\u{06FF}').contains(&c)
+        });
+        match rtl {
+            true => Direction::Rtl,
+            false => Direction::Ltr,
+        }
+    }
+}
+
+// The form a letter takes in the middle of a word, so final forms match their other form.
+pub fn fold(letter: char) -> char {
+    FINAL_FORMS
+        .iter()
+        .find(|(end, _)| *end == letter)
+        .map_or(letter, |(_, middle)| *middle)
+}
+
+// Fold every letter of a word, e.g. to compare it with another.
+pub fn fold_word(word: &str) -> String {
+    word.chars().map(fold).collect()
+}
+
+// The final form of a letter, if it has one.
+pub fn final_form(letter: char) -> Option<char> {
+    FINAL_FORMS
+        .iter()
+        .find(|(_, middle)| *middle == letter)
+        .map(|(end, _)| *end)
+}
@@ -0,0 +1,428 @@
+use crate::alphabet::Direction;
+use crate::game::{GameData, GameDataForm};
+use crate::guess::{Guess, Guesses, Match, Share};
+use crate::i18n::{Locale, Messages};
+use crate::keyboard::Layout;
+use crate::limits::{self, Action};
+use crate::router::Params;
+use crate::{challenge, csrf, state, utils};
+use fastly::http::{header, Method, StatusCode};
+use fastly::{Error, Request, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::{OpenApi, ToSchema};
+
+pub const PREFIX: &str = "/api/v1/";
+
+// The OpenAPI description, derived from the handlers and types below. It also covers the JSON
+// the game page responds to a guess with (`/{slug}?guess=`), which clients use too.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Yourdle API",
+        version = "1",
+        description = "Play and create Yourdle word games"
+    ),
+    paths(
+        get_openapi,
+        get_challenge,
+        create_identity,
+        create_game,
+        get_game,
+        get_puzzle,
+        get_stats,
+        submit_guess,
+        crate::game
+    ),
+    components(schemas(
+        GameInfo,
+        PuzzleInfo,
+        GuessResult,
+        PlayerStats,
+        GuessForm,
+        ChallengeInfo,
+        IdentityInfo,
+        ApiError,
+        GameDataForm,
+        Direction,
+        Layout,
+        Locale,
+        Match,
+        Share,
+        Guess,
+        Guesses,
+        crate::GuessResponse
+    ))
+)]
+struct ApiDoc;
+
+// Game metadata.
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameInfo {
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub total: usize,
+    // Which way to lay out the letters of a guess.
+    pub direction: Direction,
+    // The language of the game's pages, if it isn't the player's.
+    pub locale: Option<Locale>,
+}
+
+// Today's puzzle.
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PuzzleInfo {
+    pub date: String,
+    pub number: i64,
+    pub total: usize,
+    pub length: usize,
+}
+
+// A guess and how each of its letters matched today's word.
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GuessResult {
+    pub word: String,
+    pub result: Vec<Match>,
+}
+
+// A player's guesses today and overall statistics.
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStats {
+    pub date: String,
+    pub guesses: Vec<GuessResult>,
+    pub finished: bool,
+    pub won: bool,
+    pub games_played: u16,
+    pub win_rate: u16,
+    pub streak: u16,
+    pub max_streak: u16,
+    pub distribution: Vec<u16>,
+    // The best state of each letter guessed today (correct > near > wrong).
+    pub keyboard: BTreeMap<String, Match>,
+    // The last guess and the game's outcome, for screen readers, in the game's language or the
+    // one asked for in `Accept-Language`.
+    pub summary: String,
+    // Today's result to share, once the game is over.
+    pub share: Option<Share>,
+}
+
+// A guess submission.
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct GuessForm {
+    pub guess: String,
+}
+
+// A proof-of-work challenge, required to create a game.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ChallengeInfo {
+    pub challenge: String,
+}
+
+// An identity token for cross-origin clients, sent as `Authorization: Bearer <token>`.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct IdentityInfo {
+    pub token: String,
+}
+
+// An error message.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ApiError {
+    pub error: String,
+}
+
+impl From<&GameData> for GameInfo {
+    fn from(game_data: &GameData) -> Self {
+        GameInfo {
+            name: game_data.game.to_owned(),
+            slug: game_data.slug.to_owned(),
+            description: game_data.description.to_owned(),
+            total: game_data.total(),
+            direction: game_data.direction,
+            locale: game_data.locale,
+        }
+    }
+}
+
+impl PlayerStats {
+    // A player's stats, with the summary in the given language.
+    fn new(guesses: &Guesses, t: &Messages, share: Option<Share>) -> Self {
+        PlayerStats {
+            date: guesses.today.to_owned(),
+            guesses: guesses
+                .outcome
+                .iter()
+                .map(|Guess(word, result)| GuessResult {
+                    word: word.to_owned(),
+                    result: result.to_owned(),
+                })
+                .collect(),
+            finished: guesses.is_over(),
+            won: guesses.won_today(),
+            games_played: guesses.games,
+            win_rate: guesses.win_rate(),
+            streak: guesses.streak,
+            max_streak: guesses.max_streak,
+            distribution: guesses.distribution.to_owned(),
+            keyboard: guesses.letter_states(),
+            summary: guesses.summary(t),
+            share,
+        }
+    }
+}
+
+// GET /api/v1/openapi.json
+#[utoipa::path(
+    get,
+    path = "/api/v1/openapi.json",
+    responses((status = 200, description = "This OpenAPI description"))
+)]
+pub fn get_openapi(_: &mut Request, _: &Params) -> Result<Response, Error> {
+    json(StatusCode::OK, &ApiDoc::openapi())
+}
+
+// GET /api/v1/challenge
+#[utoipa::path(
+    get,
+    path = "/api/v1/challenge",
+    responses((status = 200, description = "A proof-of-work challenge", body = ChallengeInfo))
+)]
+pub fn get_challenge(_: &mut Request, _: &Params) -> Result<Response, Error> {
+    let challenge = challenge::issue().map_err(Error::msg)?;
+    json(StatusCode::OK, &ChallengeInfo { challenge })
+}
+
+// POST /api/v1/identity
+#[utoipa::path(
+    post,
+    path = "/api/v1/identity",
+    responses(
+        (status = 201, description = "An identity token for a new player", body = IdentityInfo),
+        (status = 429, description = "Rate limited", body = ApiError)
+    )
+)]
+pub fn create_identity(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    if let Some(resp) = limits::check(req, Action::Identity) {
+        return Ok(rejected(resp));
+    }
+    let token = state::issue_identity().map_err(Error::msg)?;
+    json(StatusCode::CREATED, &IdentityInfo { token })
+}
+
+// POST /api/v1/games
+#[utoipa::path(
+    post,
+    path = "/api/v1/games",
+    request_body = GameDataForm,
+    responses(
+        (status = 201, description = "The game was created", body = GameInfo),
+        (status = 400, description = "Invalid game data", body = ApiError),
+        (status = 403, description = "Invalid CSRF token or unsolved challenge", body = ApiError),
+        (status = 429, description = "Rate limited or over the daily quota", body = ApiError)
+    )
+)]
+pub fn create_game(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    if let Some(resp) = GameData::check_new(req) {
+        return Ok(rejected(resp));
+    }
+    match GameData::create(req) {
+        Ok(game_data) => json(StatusCode::CREATED, &GameInfo::from(&game_data)),
+        Err((status, e)) => Ok(error(status, e)),
+    }
+}
+
+// GET /api/v1/games/{slug}
+#[utoipa::path(
+    get,
+    path = "/api/v1/games/{slug}",
+    params(("slug" = String, Path, description = "The game's slug")),
+    responses(
+        (status = 200, description = "Game metadata", body = GameInfo),
+        (status = 404, description = "Game not found", body = ApiError)
+    )
+)]
+pub fn get_game(_: &mut Request, params: &Params) -> Result<Response, Error> {
+    match GameData::load(&params["slug"]) {
+        Ok(game_data) => json(StatusCode::OK, &GameInfo::from(&game_data)),
+        _ => Ok(not_found("Game not found")),
+    }
+}
+
+// GET /api/v1/games/{slug}/today
+#[utoipa::path(
+    get,
+    path = "/api/v1/games/{slug}/today",
+    params(("slug" = String, Path, description = "The game's slug")),
+    responses(
+        (status = 200, description = "Today's puzzle", body = PuzzleInfo),
+        (status = 404, description = "Game not found", body = ApiError)
+    )
+)]
+pub fn get_puzzle(req: &mut Request, params: &Params) -> Result<Response, Error> {
+    let mut game_data = match GameData::load(&params["slug"]) {
+        Ok(game_data) => game_data,
+        _ => return Ok(not_found("Game not found")),
+    };
+    if req.get_method() == Method::HEAD {
+        return Ok(head());
+    }
+    let (word, number, total) = game_data.get_word().map_err(|e| Error::msg(e.to_owned()))?;
+    json(
+        StatusCode::OK,
+        &PuzzleInfo {
+            date: utils::date_iso8601(),
+            number: number + 1,
+            total,
+            length: word.chars().count(),
+        },
+    )
+}
+
+// GET /api/v1/games/{slug}/stats
+#[utoipa::path(
+    get,
+    path = "/api/v1/games/{slug}/stats",
+    params(("slug" = String, Path, description = "The game's slug")),
+    responses(
+        (status = 200, description = "The player's stats", body = PlayerStats),
+        (status = 404, description = "Game not found", body = ApiError)
+    )
+)]
+pub fn get_stats(req: &mut Request, params: &Params) -> Result<Response, Error> {
+    let slug = &params["slug"];
+    let mut game_data = match GameData::load(slug) {
+        Ok(game_data) => game_data,
+        _ => return Ok(not_found("Game not found")),
+    };
+    if req.get_method() == Method::HEAD {
+        return Ok(head());
+    }
+    let (word, index, _) = game_data.get_word().map_err(|e| Error::msg(e.to_owned()))?;
+    let (user_id, cookie) = identify(req);
+    let guesses = state::load_guesses(req, slug, &user_id, word.chars().count());
+    let t = Locale::negotiate(req, game_data.locale).messages();
+    let share = guesses.share(&game_data, index + 1, &utils::game_url(req, slug));
+    Ok(with_user_cookie(
+        json(StatusCode::OK, &PlayerStats::new(&guesses, t, share))?,
+        cookie,
+    ))
+}
+
+// POST /api/v1/games/{slug}/guesses
+#[utoipa::path(
+    post,
+    path = "/api/v1/games/{slug}/guesses",
+    params(("slug" = String, Path, description = "The game's slug")),
+    request_body = GuessForm,
+    responses(
+        (status = 200, description = "The player's stats after the guess", body = PlayerStats),
+        (status = 400, description = "Invalid guess", body = ApiError),
+        (status = 403, description = "Invalid CSRF token", body = ApiError),
+        (status = 404, description = "Game not found", body = ApiError),
+        (status = 409, description = "Today's game is over", body = ApiError),
+        (status = 422, description = "The word isn't on the list, or has the wrong length", body = ApiError),
+        (status = 429, description = "Rate limited", body = ApiError)
+    )
+)]
+pub fn submit_guess(req: &mut Request, params: &Params) -> Result<Response, Error> {
+    let slug = &params["slug"];
+    if let Some(resp) = csrf::check(req).or_else(|| limits::check(req, Action::Guess)) {
+        return Ok(rejected(resp));
+    }
+    let mut game_data = match GameData::load(slug) {
+        Ok(game_data) => game_data,
+        _ => return Ok(not_found("Game not found")),
+    };
+    let guess = match req.take_body_json::<GuessForm>() {
+        Ok(form) => form.guess.to_uppercase(),
+        _ => return Ok(error(StatusCode::BAD_REQUEST, "Invalid guess")),
+    };
+    let (word, index, _) = game_data.get_word().map_err(|e| Error::msg(e.to_owned()))?;
+    let (user_id, cookie) = identify(req);
+    let mut guesses = state::load_guesses(req, slug, &user_id, word.chars().count());
+    if guesses.is_over() {
+        return Ok(error(StatusCode::CONFLICT, "Today's game is over"));
+    }
+    if guess.chars().count() != word.chars().count() {
+        return Ok(error(StatusCode::UNPROCESSABLE_ENTITY, "Wrong length"));
+    }
+    if !game_data.validate_word(&guess) {
+        return Ok(error(StatusCode::UNPROCESSABLE_ENTITY, "Not on the list"));
+    }
+    guesses.update(slug, &user_id, Guess::new(&guess, &word))?;
+    let t = Locale::negotiate(req, game_data.locale).messages();
+    let share = guesses.share(&game_data, index + 1, &utils::game_url(req, slug));
+    let mut resp = with_user_cookie(
+        json(StatusCode::OK, &PlayerStats::new(&guesses, t, share))?,
+        cookie,
+    );
+    state::save_progress(&mut resp, &guesses, slug, &user_id).map_err(Error::msg)?;
+    Ok(resp)
+}
+
+// Identify the player by a bearer token (cross-origin clients) or the cookie, returning the user ID
+// and the cookie to set, if any.
+fn identify(req: &Request) -> (String, Option<String>) {
+    match state::find_bearer_id(req) {
+        Some(user_id) => (user_id, None),
+        None => {
+            let user_id = state::get_user_id(req.get_header_str("cookie").unwrap_or_default());
+            let cookie = state::set_user_id(&user_id);
+            (user_id, Some(cookie))
+        }
+    }
+}
+
+// Response helpers.
+fn json<T: Serialize>(status: StatusCode, body: &T) -> Result<Response, Error> {
+    Ok(Response::from_status(status)
+        .with_header(header::CACHE_CONTROL, "private, no-store")
+        .with_body_json(body)?)
+}
+
+fn with_user_cookie(resp: Response, cookie: Option<String>) -> Response {
+    match cookie {
+        Some(cookie) => resp.with_header(header::SET_COOKIE, cookie),
+        None => resp,
+    }
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    json(
+        status,
+        &ApiError {
+            error: message.to_owned(),
+        },
+    )
+    .unwrap_or_else(|_| Response::from_status(status))
+}
+
+// Rejections from the CSRF and rate limit checks shared with the HTML routes, as JSON errors
+// like the API's own. The status and other headers, e.g. `Retry-After`, are kept.
+fn rejected(mut resp: Response) -> Response {
+    let reason = resp.take_body_str();
+    let error = match reason.trim() {
+        "" => resp.get_status().canonical_reason().unwrap_or_default(),
+        reason => reason,
+    };
+    resp.set_body_json(&ApiError {
+        error: error.to_owned(),
+    })
+    .ok();
+    resp.set_header(header::CACHE_CONTROL, "private, no-store");
+    resp
+}
+
+// Respond to HEAD requests without loading today's puzzle, which may reset the game.
+fn head() -> Response {
+    Response::from_status(StatusCode::OK)
+        .with_content_type(fastly::mime::APPLICATION_JSON)
+        .with_header(header::CACHE_CONTROL, "private, no-store")
+}
+
+pub fn not_found(message: &str) -> Response {
+    error(StatusCode::NOT_FOUND, message)
+}
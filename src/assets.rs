@@ -0,0 +1,129 @@
+use crate::router::Params;
+use fastly::http::{header, StatusCode};
+use fastly::{Error, Request, Response};
+
+// Hashed paths change with the content, so they can be cached for a year.
+const IMMUTABLE_CACHE: &str = "public, max-age=31536000, immutable";
+pub const LONG_CACHE: &str = "public, max-age=3600, must-revalidate";
+
+// A static file embedded by build.rs, served under its original path and a content-hashed path.
+pub struct Asset {
+    pub path: &'static str,
+    pub hashed_path: &'static str,
+    pub hash: &'static str,
+    pub content_type: &'static str,
+    pub body: &'static [u8],
+    // Precompressed variants, for text files that compress well.
+    pub gzip: Option<&'static [u8]>,
+    pub brotli: Option<&'static [u8]>,
+}
+
+// The `ASSETS` list, and a constant with the hashed path of each asset for the templates
+// (e.g. `STYLE_CSS` for `/style.css`).
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+impl Asset {
+    // Pick the smallest variant the client accepts: its content encoding, if any, and body.
+    fn negotiate(&self, accept_encoding: &str) -> (Option<&'static str>, &'static [u8]) {
+        match (self.brotli, self.gzip) {
+            (Some(brotli), _) if accepts(accept_encoding, "br") => (Some("br"), brotli),
+            (_, Some(gzip)) if accepts(accept_encoding, "gzip") => (Some("gzip"), gzip),
+            _ => (None, self.body),
+        }
+    }
+}
+
+// Serve a static asset, or 304 if the client's copy is current.
+pub fn serve(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    let path = req.get_path();
+    let asset = match ASSETS
+        .iter()
+        .find(|asset| asset.path == path || asset.hashed_path == path)
+    {
+        Some(asset) => asset,
+        None => return Ok(Response::from_status(StatusCode::NOT_FOUND)),
+    };
+    let cache_control = match path == asset.hashed_path {
+        true => IMMUTABLE_CACHE,
+        false => LONG_CACHE,
+    };
+    let (encoding, body) = asset.negotiate(
+        req.get_header_str(header::ACCEPT_ENCODING)
+            .unwrap_or_default(),
+    );
+    // Each encoding is a different representation, so it needs its own ETag.
+    let etag = match encoding {
+        Some(encoding) => format!("\"{}-{}\"", asset.hash, encoding),
+        None => format!("\"{}\"", asset.hash),
+    };
+    let not_modified = req
+        .get_header_str(header::IF_NONE_MATCH)
+        .is_some_and(|etags| {
+            etags
+                .split(',')
+                .map(|etag| etag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == etag || tag == "*")
+        });
+    let mut resp = Response::from_status(StatusCode::OK)
+        .with_header(header::ETAG, etag)
+        .with_header(header::CACHE_CONTROL, cache_control);
+    if asset.gzip.is_some() || asset.brotli.is_some() {
+        resp.set_header(header::VARY, "Accept-Encoding");
+    }
+    if not_modified {
+        return Ok(resp.with_status(StatusCode::NOT_MODIFIED));
+    }
+    if let Some(encoding) = encoding {
+        resp.set_header(header::CONTENT_ENCODING, encoding);
+    }
+    Ok(resp
+        .with_header(header::CONTENT_TYPE, asset.content_type)
+        .with_body(body))
+}
+
+// Whether an `Accept-Encoding` header accepts an encoding (with a non-zero quality). The encoding
+// itself takes precedence over `*`, so it can be refused while others are accepted.
+fn accepts(accept_encoding: &str, encoding: &str) -> bool {
+    let codings: Vec<(&str, f32)> = accept_encoding
+        .split(',')
+        .map(|coding| {
+            let mut parts = coding.split(';').map(str::trim);
+            let name = parts.next().unwrap_or_default();
+            let quality = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (name, quality)
+        })
+        .collect();
+    codings
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(encoding))
+        .or_else(|| codings.iter().find(|(name, _)| *name == "*"))
+        .is_some_and(|(_, quality)| *quality > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_listed_encodings() {
+        assert!(accepts("gzip, deflate, br", "br"));
+        assert!(accepts("GZIP", "gzip"));
+        assert!(accepts("br;q=0.5, gzip;q=1.0", "br"));
+        assert!(!accepts("gzip, deflate", "br"));
+        assert!(!accepts("", "gzip"));
+    }
+
+    #[test]
+    fn accepts_respects_zero_quality() {
+        assert!(!accepts("br;q=0, gzip", "br"));
+        assert!(!accepts("br; q=0", "br"));
+        assert!(accepts("*", "br"));
+        assert!(!accepts("*;q=0", "br"));
+        // The encoding itself wins over `*`.
+        assert!(!accepts("br;q=0, *", "br"));
+        assert!(accepts("gzip;q=0, *", "br"));
+    }
+}
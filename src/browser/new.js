@@ -3,6 +3,50 @@ const createdSuccessfully = document.getElementById('createdSuccessfully')
 const wordCount = document.getElementById('wordCount')
 const submit = document.getElementById('make')
 const gameLink = document.getElementById('gameLink')
+const embed = document.getElementById('embed')
+const embedSnippet = document.getElementById('embedSnippet')
+const copyEmbed = document.getElementById('copyEmbed')
+
+// Solve a proof-of-work challenge: find a solution for which the SHA-256 digest
+// of `${challenge}:${solution}` starts with enough zero bits.
+const solveChallenge = async challenge => {
+  const [payload] = challenge.split('.')
+  const { d: difficulty } = JSON.parse(
+    atob(payload.replace(/-/g, '+').replace(/_/g, '/'))
+  )
+  const encoder = new TextEncoder()
+  for (let solution = 0; ; solution++) {
+    const digest = new Uint8Array(
+      await crypto.subtle.digest(
+        'SHA-256',
+        encoder.encode(`${challenge}:${solution}`)
+      )
+    )
+    let zeroBits = 0
+    for (const byte of digest) {
+      zeroBits += Math.clz32(byte) - 24
+      if (byte) break
+    }
+    if (zeroBits >= difficulty) return `${solution}`
+  }
+}
+// Start solving straight away, while the form is being filled in.
+let challenge = document.getElementById('challenge').value
+let solution = solveChallenge(challenge).catch(() => '')
+
+// A challenge can only be used once, so get and start solving a new one after a
+// failed attempt.
+const newChallenge = () =>
+  fetch('/api/v1/challenge')
+    .then(res => {
+      if (res.ok) return res.json()
+      throw new Error(res.status)
+    })
+    .then(res => {
+      challenge = res.challenge
+      solution = solveChallenge(challenge).catch(() => '')
+    })
+    .catch(() => {})
 
 const inputs = ['game', 'description', 'words'].reduce((acc, input) => {
   acc[input] = {
@@ -26,40 +70,42 @@ const hideValidation = ({ validation }) => {
 inputs.game.validate = async () => {
   const game = inputs.game.field.value.trim()
   if (game.length < 3)
-    return validationMessage(inputs.game, 'too short')
+    return validationMessage(inputs.game, messages.tooShort)
   const { ok } = await fetch('/validate', {
     method: 'POST',
-    headers: { 'Content-Type': 'application/json' },
+    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken },
     body: JSON.stringify({ game })
   })
   return ok
     ? hideValidation(inputs.game)
-    : validationMessage(inputs.game, 'game already exists')
+    : validationMessage(inputs.game, messages.gameExists)
 }
 
 inputs.description.validate = async () => {
   if (inputs.description.field.value.trim().length < 10)
-    return validationMessage(inputs.description, 'too short')
+    return validationMessage(inputs.description, messages.tooShort)
   return hideValidation(inputs.description)
 }
 
 inputs.words.validate = () => {
   if (inputs.words.field.value.length < 3) {
     wordCount.innerText = ''
-    return validationMessage(inputs.words, 'too short')
+    return validationMessage(inputs.words, messages.tooShort)
   }
   let sanitized = inputs.words.field.value
   // Replace whitespace and punctuation with a single space.
-  // Remove non-ASCII alphabet characters.
+  // Remove anything but letters, in any alphabet.
   // Ensure each word is at least 3 and at most 10 letters long.
   sanitized = sanitized
+    .normalize('NFC')
     .replace(/[\s!"#$%&'()*+,-./:;<=>?@[\\\]^_`{|}~]/g, ' ')
-    .replace(/[^a-zA-Z ]/g, '')
-    .replace(/\b[a-zA-Z]{1,2}\b|\b[a-zA-Z]{11,}\b/g, '')
+    .replace(/[^\p{L}\s]/gu, '')
     .toUpperCase()
     .trim()
+    .split(/\s+/)
+    .filter(word => [...word].length >= 3 && [...word].length <= 10)
   // Ensure we have a maximum of 365 unique words.
-  sanitized = [...new Set(sanitized.split(/\s+/))].slice(0, 365)
+  sanitized = [...new Set(sanitized)].slice(0, 365)
   if (sanitized.length >= 2) {
     inputs.words.field.value = sanitized.join(' ')
     wordCount.innerText = `[${sanitized.length}]`
@@ -90,23 +136,48 @@ submit.addEventListener('click', async e => {
   const gameData = {
     game: inputs.game.field.value.trim(),
     description: inputs.description.field.value.trim(),
-    words: inputs.words.field.value.trim()
+    words: inputs.words.field.value.trim(),
+    origins: document.getElementById('origins').value.trim(),
+    layout: document.getElementById('layout').value,
+    // Left out to match the word list.
+    direction: document.getElementById('direction').value || undefined,
+    // Left out to use the player's language.
+    locale: document.getElementById('locale').value || undefined
   }
   // Attempt to create the game.
   const res = await fetch('/new', {
     method: 'POST',
-    headers: { 'Content-Type': 'application/json' },
-    body: JSON.stringify(gameData)
+    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken },
+    body: JSON.stringify({
+      ...gameData,
+      challenge,
+      solution: await solution
+    })
   })
   if (!res.ok) {
     processEventTargets()
-    return announce(`Something went wrong 🥲\nTry again later`)
+    newChallenge()
+    return announce(
+      res.status === 429 ? messages.enoughGames : messages.tryLater
+    )
   }
   const gameSlug = await res.text()
   const gameUrl = `${window.location.origin}/${gameSlug}`
   gameLink.href = gameUrl
   gameLink.innerText = `${window.location.host}/${gameSlug}`
-  clipboard.value = `I made a word game! Check out "${gameData.game}" at: ${gameUrl}`
+  clipboard.value = fmt(messages.madeGame, gameData.game, gameUrl)
+  // Games can only be embedded on the sites they were made for.
+  if (gameData.origins) {
+    const title = gameData.game.replace(/[&"<>]/g, c => `&#${c.charCodeAt(0)};`)
+    embedSnippet.value = `<iframe src="${gameUrl}/embed" width="400" height="640" style="border:0" title="${title}" loading="lazy"></iframe>`
+    embed.style.display = 'block'
+  }
   newForm.remove()
   createdSuccessfully.style.display = 'block'
 })
+
+copyEmbed.addEventListener('click', e => {
+  e.preventDefault()
+  e.stopPropagation()
+  doClipboard(embedSnippet.value)
+})
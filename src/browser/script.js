@@ -15,7 +15,18 @@ const sendFeedback = document.getElementById('sendFeedback')
 const body = document.querySelector('body')
 const activeRow = document.getElementsByClassName('active')
 const clipboard = document.getElementById('clipboard')
+const summary = document.getElementById('summary')
 const buttons = {}
+const csrfToken = document.querySelector('meta[name="csrf-token"]')?.content
+// The page's messages, in its language (see `i18n.rs`).
+const messages = JSON.parse(document.documentElement.dataset.messages || '{}')
+
+// Fill in a message's numbered placeholders, e.g. "Guess {0}", in a single pass so
+// placeholders in the arguments are left as they are (see `i18n::fill`).
+const fmt = (message, ...args) =>
+  message.replace(/\{(\d+)\}/g, (placeholder, i) =>
+    i < args.length ? `${args[i]}` : placeholder
+  )
 
 const [, gameSlug] = window.location.pathname.split('/')
 const origin = window.location.origin
@@ -42,6 +53,13 @@ const announce = msg => {
   }, 3000)
 }
 
+// How each match is described to screen readers (see `Match::description`).
+const descriptions = {
+  correct: messages.correct,
+  near: messages.near,
+  wrong: messages.wrong
+}
+
 const recordResult = stats => {
   const [guess, outcome] = stats.outcome[stats.outcome.length - 1]
   if (!activeRow.length) return
@@ -50,22 +68,31 @@ const recordResult = stats => {
   const activeTiles = activeRow.item(0).children
   outcome.forEach((o, idx) => {
     const tile = activeTiles.item(idx)
-    const currState = tile.dataset.state
-    buttons[guess[idx].toLowerCase()].dataset.state =
-      currState && o < currState ? currState : o
     tile.classList.add('flip')
     tile.dataset.state = o
+    tile.setAttribute('aria-label', `${guess[idx]}, ${descriptions[o]}`)
     if (o !== 'correct') win = false
   })
+  // Colour the keyboard with the best state of each letter, worked out by the server.
+  Object.entries(stats.keyboard).forEach(([letter, state]) => {
+    const button = buttons[letter.toLowerCase()]
+    if (!button) return
+    button.dataset.state = state
+    button.setAttribute('aria-label', `${letter}, ${descriptions[state]}`)
+  })
+  if (summary) summary.innerText = stats.summary
   if (win) {
-    announce('You win ❤️')
+    announce(messages.youWin)
     setTimeout(() => {
       updateStats(stats)
     }, 2000)
   } else if (activeRow.item(0).nextElementSibling) {
     activeRow.item(0).nextElementSibling.classList.add('active')
   } else {
-    announce('Better luck next time 😓')
+    announce(messages.betterLuck)
+    setTimeout(() => {
+      updateStats(stats)
+    }, 2000)
   }
   activeRow.item(0).classList.remove('active')
 }
@@ -86,22 +113,10 @@ const updateStats = state => {
     distro.forEach((bar, idx) => {
       bar.children[0].innerText = state.distribution[idx]
     })
-    if (state.today === state.lastWin) {
-      const c = {
-        correct: '🟢',
-        near: '🟡',
-        wrong: '⚫'
-      }
-      const text = [
-        `I took ${state.outcome.length} ${
-          state.outcome.length === 1 ? `guess` : `guesses`
-        } at today's ${gameTitle.value}:`
-      ]
-      state.outcome.forEach(o => {
-        text.push(o[1].map(outcome => c[outcome]).join(' '))
-      })
-      text.push(`${origin}/${gameSlug}`)
-      clipboard.value = text.join('\n')
+    // The result to share, put together by the server once the game is over.
+    if (state.share) {
+      share.dataset.share = state.share.text
+      share.dataset.shareContrast = state.share.contrast
       share.style.display = 'block'
     } else share.style.display = 'none'
   }
@@ -125,7 +140,7 @@ const submitFeedback = () => {
   }
   fetch('/feedback', {
     method: 'POST',
-    headers: { 'Content-Type': 'application/json' },
+    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken },
     body: JSON.stringify({
       path: gameSlug,
       feedback: fb
@@ -133,9 +148,9 @@ const submitFeedback = () => {
   })
     .then(res => {
       if (!res.ok) throw new Error(res.status)
-      clearAndClose(`Thank you!`)
+      clearAndClose(messages.thanks)
     })
-    .catch(() => clearAndClose(`Something went wrong 🥲\nTry again later`))
+    .catch(() => clearAndClose(messages.tryLater))
 }
 
 const fallbackCopyTextToClipboard = text => {
@@ -161,7 +176,7 @@ const copyTextToClipboard = async text =>
 
 const doClipboard = text =>
   copyTextToClipboard(text).then(ok => {
-    announce(ok ? `Copied to clipboard\n📋` : `Copy:\n\n${text}`)
+    announce(ok ? messages.copied : `${messages.copy}\n\n${text}`)
   })
 
 document.addEventListener('keydown', e => {
@@ -178,12 +193,16 @@ document.addEventListener(
       switch (e.target) {
         case share:
           e.preventDefault()
-          doClipboard(clipboard.value)
+          doClipboard(
+            body.classList.contains('hiContrast')
+              ? share.dataset.shareContrast
+              : share.dataset.share
+          )
           break
         case shareThis:
           e.preventDefault()
           doClipboard(
-            `Check out this fun word game, ${gameTitle.value}: ${origin}/${gameSlug}`
+            fmt(messages.checkOut, gameTitle.value, `${origin}/${gameSlug}`)
           )
           break
         case colorMode:
@@ -244,9 +263,10 @@ if (!['new', 'feedback', 'validate'].includes(gameSlug)) {
               return t.innerText
             })
             .join('')
-          fetch(`${gameSlug}?guess=${word}`, {
+          fetch(`${window.location.pathname}?guess=${encodeURIComponent(word)}`, {
             method: 'GET',
-            credentials: 'same-origin'
+            credentials: 'same-origin',
+            headers: { 'X-CSRF-Token': csrfToken }
           })
             .then(res => {
               if (res.ok) return res.json()
@@ -256,8 +276,10 @@ if (!['new', 'feedback', 'validate'].includes(gameSlug)) {
             .catch(({ message }) =>
               announce(
                 message === '404'
-                  ? `That's not on the list`
-                  : `Something went wrong`
+                  ? messages.notOnList
+                  : message === '429'
+                  ? messages.slowDown
+                  : messages.wentWrong
               )
             )
           break
@@ -275,7 +297,7 @@ if (!['new', 'feedback', 'validate'].includes(gameSlug)) {
       }
     })
   }
-  if (stats && stats.getAttribute('data-won-today') === 'true') {
+  if (stats && stats.getAttribute('data-over') === 'true') {
     updateStats()
   }
 }
@@ -0,0 +1,105 @@
+use crate::{config, token, utils};
+use fastly::kv_store::{InsertMode, KVStoreError};
+use fastly::KVStore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use uuid::Uuid;
+
+const CHALLENGE_TTL: i64 = 60 * 60; // 1 hour
+const USED_STORE_NAME: &str = "yourdle-stats";
+const DIFFICULTY: u32 = 16;
+
+// A proof-of-work challenge, issued with the new game form.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Challenge {
+    #[serde(rename = "n")]
+    nonce: String,
+    #[serde(rename = "i")]
+    issued: i64,
+    #[serde(rename = "d")]
+    difficulty: u32,
+}
+
+// Issue a signed challenge, with the difficulty configurable as `challenge-difficulty`.
+pub fn issue() -> Result<String, &'static str> {
+    token::sign(&Challenge {
+        nonce: Uuid::new_v4().to_string(),
+        issued: utils::timestamp_now(),
+        difficulty: config::get("challenge-difficulty")
+            .and_then(|d| d.parse().ok())
+            .unwrap_or(DIFFICULTY),
+    })
+}
+
+// Check a challenge was issued by us recently.
+fn open(token: &str) -> Result<Challenge, &'static str> {
+    let challenge: Challenge = token::verify(token)?;
+    if utils::timestamp_now() - challenge.issued > CHALLENGE_TTL {
+        return Err("Challenge expired");
+    }
+    Ok(challenge)
+}
+
+// Check a challenge is valid and solved, i.e. the SHA-256 digest of `{token}:{solution}`
+// starts with at least as many zero bits as the challenge difficulty. A solved challenge can
+// only be used once, so it should be marked used once whatever it's for is sure to go ahead.
+pub fn verify(token: &str, solution: &str) -> Result<Challenge, &'static str> {
+    let challenge = open(token)?;
+    let digest = Sha256::digest(format!("{}:{}", token, solution).as_bytes());
+    if zero_bits(&digest) < challenge.difficulty {
+        return Err("Challenge not solved");
+    }
+    Ok(challenge)
+}
+
+// The number of leading zero bits in a digest.
+fn zero_bits(digest: &[u8]) -> u32 {
+    let mut zero_bits = 0;
+    for byte in digest {
+        zero_bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    zero_bits
+}
+
+impl Challenge {
+    // Record a challenge as used until it expires, failing if it already was.
+    pub fn mark_used(&self) -> Result<(), &'static str> {
+        let used_store = match KVStore::open(USED_STORE_NAME) {
+            Ok(Some(used_store)) => used_store,
+            _ => return Err("Could not open KV store"),
+        };
+        match used_store
+            .build_insert()
+            .mode(InsertMode::Add)
+            .time_to_live(Duration::from_secs(CHALLENGE_TTL as u64))
+            .execute(&format!("challenge-{}", self.nonce), "")
+        {
+            Ok(()) => Ok(()),
+            Err(KVStoreError::ItemPreconditionFailed) => Err("Challenge already used"),
+            _ => Err("Could not record challenge"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_bits_counts_leading_zeros_across_bytes() {
+        assert_eq!(zero_bits(&[0, 0, 0x0f, 0xff]), 20);
+        assert_eq!(zero_bits(&[0, 0x01, 0]), 15);
+        assert_eq!(zero_bits(&[0x80, 0]), 0);
+    }
+
+    #[test]
+    fn zero_bits_stops_at_the_first_set_bit() {
+        assert_eq!(zero_bits(&[0x10, 0, 0]), 3);
+        assert_eq!(zero_bits(&[0; 32]), 256);
+        assert_eq!(zero_bits(&[]), 0);
+    }
+}
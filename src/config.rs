@@ -0,0 +1,16 @@
+use fastly::ConfigStore;
+
+const CONFIG_STORE_NAME: &str = "yourdle-config";
+
+// Returns a configuration value, if set.
+pub fn get(key: &str) -> Option<String> {
+    match ConfigStore::try_open(CONFIG_STORE_NAME) {
+        Ok(config_store) => config_store.get(key),
+        _ => None,
+    }
+}
+
+// Whether today's progress is carried in a signed cookie rather than the stats store.
+pub fn stateless() -> bool {
+    get("stateless").is_some_and(|value| value == "true")
+}
@@ -0,0 +1,68 @@
+use crate::api;
+use crate::config;
+use crate::game::GameData;
+use crate::state;
+use fastly::http::{header, Method, StatusCode};
+use fastly::{Request, Response};
+
+const MAX_AGE: &str = "86400"; // 1 day
+
+// An API request from another origin that is allowed to read the response.
+pub struct Cors {
+    origin: String,
+    preflight: bool,
+}
+
+// Check if a request comes from an origin allowed to call the API. Origins can be allowed for
+// every game with the space-separated `cors-origins` config key (`*` allows any origin), or per game.
+pub fn check(req: &Request) -> Option<Cors> {
+    let path = req.get_path().strip_prefix(api::PREFIX)?;
+    let origin = req.get_header_str(header::ORIGIN)?;
+    let mut allowed: Vec<String> = config::get("cors-origins")
+        .map(|origins| origins.split_whitespace().map(String::from).collect())
+        .unwrap_or_default();
+    if let ["games", slug, ..] = path.split('/').collect::<Vec<_>>()[..] {
+        if let Ok(game_data) = GameData::load(slug) {
+            allowed.extend(game_data.origins);
+        }
+    }
+    if !allowed
+        .iter()
+        .any(|allowed| allowed == "*" || allowed == origin)
+    {
+        return None;
+    }
+    Some(Cors {
+        origin: origin.to_owned(),
+        preflight: req.get_method() == Method::OPTIONS
+            && req
+                .get_header(header::ACCESS_CONTROL_REQUEST_METHOD)
+                .is_some(),
+    })
+}
+
+impl Cors {
+    // Add the CORS headers to a response. Cross-origin clients are identified with a bearer token
+    // rather than cookies, so credentials are never allowed.
+    pub fn apply(&self, mut resp: Response) -> Response {
+        resp.set_header(header::ACCESS_CONTROL_ALLOW_ORIGIN, self.origin.as_str());
+        resp.append_header(header::VARY, "Origin");
+        if self.preflight && resp.get_status() == StatusCode::NO_CONTENT {
+            // The router answers OPTIONS with the methods allowed for the route.
+            if let Some(allow) = resp.get_header_str(header::ALLOW).map(String::from) {
+                resp.set_header(header::ACCESS_CONTROL_ALLOW_METHODS, allow);
+            }
+            resp.set_header(
+                header::ACCESS_CONTROL_ALLOW_HEADERS,
+                format!("Authorization, Content-Type, {}", state::PROGRESS_HEADER),
+            );
+            resp.set_header(header::ACCESS_CONTROL_MAX_AGE, MAX_AGE);
+        } else {
+            resp.set_header(
+                header::ACCESS_CONTROL_EXPOSE_HEADERS,
+                format!("Retry-After, {}", state::PROGRESS_HEADER),
+            );
+        }
+        resp
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{state, token};
+use fastly::http::{header, StatusCode, Url};
+use fastly::{Request, Response};
+
+pub const HEADER_NAME: &str = "x-csrf-token";
+
+// Generate a CSRF token bound to a user ID, to be embedded in the HTML templates.
+pub fn generate(user_id: &str) -> String {
+    token::sign(&user_id).unwrap_or_default()
+}
+
+// Check a state-changing request was made from one of our pages, and respond with 403 if not.
+pub fn check(req: &Request) -> Option<Response> {
+    check_token(req, req.get_header_str(HEADER_NAME))
+}
+
+// Check a form post from one of our pages, where the token is a form field rather than a header.
+pub fn check_form(req: &Request, token: &str) -> Option<Response> {
+    check_token(req, Some(token))
+}
+
+fn check_token(req: &Request, token: Option<&str>) -> Option<Response> {
+    // Browsers don't attach bearer tokens by themselves, so cross-origin clients using one are exempt.
+    if state::find_bearer_id(req).is_some() {
+        return None;
+    }
+    // Reject requests made from other origins.
+    if let Some(origin) = req.get_header_str(header::ORIGIN) {
+        let origin_host = Url::parse(origin)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from));
+        if origin_host.as_deref() != req.get_url().host_str() {
+            return Some(forbidden("Cross-origin request not allowed"));
+        }
+    }
+    // The token must have been issued for the user ID in the cookie.
+    let cookie = req.get_header_str("cookie").unwrap_or_default();
+    let user_id = match state::find_user_id(cookie) {
+        Some(user_id) => user_id,
+        None => return Some(forbidden("Missing user ID")),
+    };
+    match token.map(token::verify::<String>) {
+        Some(Ok(token_user_id)) if token_user_id == user_id => None,
+        Some(_) => Some(forbidden("Invalid CSRF token")),
+        None => Some(forbidden("Missing CSRF token")),
+    }
+}
+
+fn forbidden(reason: &str) -> Response {
+    Response::from_status(StatusCode::FORBIDDEN).with_body_text_plain(&format!("{}\n", reason))
+}
@@ -1,8 +1,17 @@
-use crate::utils::{get_days_since, sanitize_as_words, timestamp_now, truncate_to_chars};
+use crate::alphabet::{self, Direction};
+use crate::i18n::Locale;
+use crate::keyboard::{KeyRow, Layout};
+use crate::limits::{self, Action};
+use crate::utils::{
+    get_days_since, sanitize_as_origins, sanitize_as_words, timestamp_now, truncate_to_chars,
+};
+use crate::{challenge, csrf};
 use el_slugify::slugify;
-use fastly::KVStore;
+use fastly::http::StatusCode;
+use fastly::kv_store::KVStoreError;
+use fastly::{KVStore, Request, Response};
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use utoipa::ToSchema;
 
 const KV_STORE_NAME: &str = "yourdle";
 
@@ -14,23 +23,43 @@ pub struct GameData {
     words: Vec<String>,
     #[serde(default = "timestamp_now")]
     starts: i64,
+    // Other origins allowed to call the API for this game (CORS) and to embed it.
+    #[serde(default)]
+    pub origins: Vec<String>,
+    #[serde(default)]
+    pub layout: Layout,
+    #[serde(default)]
+    pub direction: Direction,
+    // The language of the game's pages, or the player's if not set.
+    #[serde(default)]
+    pub locale: Option<Locale>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, ToSchema)]
 pub struct GameDataForm {
     pub game: String,
     pub description: Option<String>,
     pub words: Option<String>,
+    pub origins: Option<String>,
+    pub layout: Option<Layout>,
+    // Derived from the word list if not given.
+    pub direction: Option<Direction>,
+    pub locale: Option<Locale>,
+    // Required to create a game, but not to check a name is available.
+    #[schema(required = true, nullable = false)]
+    pub challenge: Option<String>,
+    #[schema(required = true, nullable = false)]
+    pub solution: Option<String>,
 }
 
 impl GameData {
     // Any validation of form data submitted for the creation of a new game.
     pub fn from_form(form: GameDataForm) -> Result<Self, &'static str> {
-        if form.game.len() < 3 {
+        if form.game.chars().count() < 3 {
             return Err("Name too short");
         }
         let description = form.description.unwrap_or_default();
-        if description.len() < 10 {
+        if description.chars().count() < 10 {
             return Err("Description too short");
         }
         let words = sanitize_as_words(form.words.unwrap_or_default());
@@ -45,26 +74,63 @@ impl GameData {
             game: game.to_string(),
             slug: slugify(&game),
             description: truncate_to_chars(&description, 140).to_string(),
+            direction: form.direction.unwrap_or_else(|| Direction::detect(&words)),
             words,
             starts: timestamp_now(),
+            origins: sanitize_as_origins(&form.origins.unwrap_or_default()),
+            layout: form.layout.unwrap_or_default(),
+            locale: form.locale,
         })
     }
 
     // Load the game data from KV store.
     pub fn load(slug: &str) -> Result<GameData, &str> {
         match KVStore::open(KV_STORE_NAME) {
-            Ok(Some(game_store)) => match game_store.lookup_str(slug) {
-                Ok(Some(value)) => Ok(serde_json::from_str::<GameData>(&value).unwrap()),
+            Ok(Some(game_store)) => match game_store.lookup(slug) {
+                Ok(mut value) => {
+                    Ok(serde_json::from_str::<GameData>(&value.take_body().into_string()).unwrap())
+                }
                 _ => Err("Could not load game data"),
             },
             _ => Err("Could not open KV store"),
         }
     }
 
+    // Check a request to create a game was made from one of our pages (or by an API client), and
+    // isn't rate limited or over the daily quota. Rejected requests get a response to send as is.
+    pub fn check_new(req: &Request) -> Option<Response> {
+        csrf::check(req)
+            .or_else(|| limits::check(req, Action::New))
+            .or_else(|| limits::check_quota(req))
+    }
+
+    // Create and save a game from the new game form or the API, once its proof-of-work challenge
+    // is solved, and count it against the daily quota. The challenge is only used up once the
+    // form is valid, so it can be fixed and sent again.
+    pub fn create(req: &mut Request) -> Result<GameData, (StatusCode, &'static str)> {
+        let form = req
+            .take_body_json::<GameDataForm>()
+            .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid game data"))?;
+        let challenge = challenge::verify(
+            form.challenge.as_deref().unwrap_or_default(),
+            form.solution.as_deref().unwrap_or_default(),
+        )
+        .map_err(|e| (StatusCode::FORBIDDEN, e))?;
+        let mut game_data = GameData::from_form(form).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        challenge
+            .mark_used()
+            .map_err(|e| (StatusCode::FORBIDDEN, e))?;
+        game_data
+            .save()
+            .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e))?;
+        limits::count_quota(req);
+        Ok(game_data)
+    }
+
     // Save the game data to KV store – randomize words, start the game today and return the first word.
-    pub fn save(&mut self) -> Result<String, &str> {
+    pub fn save(&mut self) -> Result<String, &'static str> {
         match KVStore::open(KV_STORE_NAME) {
-            Ok(Some(mut game_store)) => {
+            Ok(Some(game_store)) => {
                 self.starts = timestamp_now();
                 fastrand::shuffle(&mut self.words);
                 return match serde_json::to_string(&self) {
@@ -95,13 +161,27 @@ impl GameData {
         ))
     }
 
+    // The number of the current game, starting from 1.
+    pub fn number(&self) -> i64 {
+        get_days_since(self.starts) + 1
+    }
+
+    // The rows of the game's on-screen keyboard.
+    pub fn keyboard(&self) -> Vec<KeyRow> {
+        self.layout.keyboard(&self.words)
+    }
+
+    // The total number of words in the game.
+    pub fn total(&self) -> usize {
+        self.words.len()
+    }
+
     // Validate if a word is in the game's list of words.
     pub fn validate_word(&self, word: &str) -> bool {
+        let word = alphabet::fold_word(&word.to_lowercase());
         self.words
             .iter()
-            .map(|w| w.to_lowercase())
-            .collect::<Vec<_>>()
-            .contains(&word.to_lowercase().to_owned())
+            .any(|w| alphabet::fold_word(&w.to_lowercase()) == word)
     }
 
     // Check the game doesn't already exist.
@@ -112,6 +192,9 @@ impl GameData {
             "new".to_string(),
             "validate".to_string(),
             "feedback".to_string(),
+            "api".to_string(),
+            "oembed".to_string(),
+            "challenge".to_string(),
         ]
         .contains(&slug)
         {
@@ -119,27 +202,11 @@ impl GameData {
         }
         match KVStore::open(KV_STORE_NAME) {
             Ok(Some(game_store)) => match game_store.lookup(&slug) {
-                Ok(None) => Ok(slug),
-                Ok(Some(_)) => Err("Game already exists"),
+                Err(KVStoreError::ItemNotFound) => Ok(slug),
+                Ok(_) => Err("Game already exists"),
                 _ => Err("Could not load game data"),
             },
             _ => Err("Could not open KV store"),
         }
     }
 }
-
-impl Display for GameData {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        let word_idx = get_days_since(self.starts);
-        write!(
-            f,
-            "{}",
-            include_str!("browser/start.html")
-                .replace("{GAME}", &self.game)
-                .replace("{DESCRIPTION}", &self.description)
-                .replace("{SLUG}", &self.slug)
-                .replace("{CURRENT}", &(word_idx + 1).to_string())
-                .replace("{TOTAL}", &self.words.len().to_string())
-        )
-    }
-}
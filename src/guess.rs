@@ -1,12 +1,18 @@
-use crate::utils;
+use crate::alphabet::Direction;
+use crate::game::GameData;
+use crate::i18n::{self, Messages};
+use crate::{alphabet, config, token, utils};
+use fastly::kv_store::{InsertMode, KVStoreError};
 use fastly::KVStore;
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
 
 const KV_STORE_NAME: &str = "yourdle-stats";
 pub const TRIES: usize = 6;
+const MAX_ATTEMPTS: usize = 5;
 
-#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Match {
     Correct,
@@ -14,43 +20,165 @@ pub enum Match {
     Wrong,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+impl Match {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Match::Correct => "correct",
+            Match::Near => "near",
+            Match::Wrong => "wrong",
+        }
+    }
+
+    // Describes a match for screen readers.
+    pub fn description(&self, t: &Messages) -> &'static str {
+        match self {
+            Match::Correct => t.client.correct,
+            Match::Near => t.client.near,
+            Match::Wrong => t.client.wrong,
+        }
+    }
+
+    // A match in a result pattern (see `Guesses::pattern`).
+    fn code(&self) -> char {
+        match self {
+            Match::Correct => 'c',
+            Match::Near => 'n',
+            Match::Wrong => 'w',
+        }
+    }
+
+    fn from_code(code: char) -> Option<Match> {
+        match code {
+            'c' => Some(Match::Correct),
+            'n' => Some(Match::Near),
+            'w' => Some(Match::Wrong),
+            _ => None,
+        }
+    }
+
+    // How a match is shared, in the normal or high contrast colours.
+    pub fn emoji(&self, contrast: bool) -> &'static str {
+        match (self, contrast) {
+            (Match::Correct, false) => "🟢",
+            (Match::Correct, true) => "🟠",
+            (Match::Near, false) => "🟡",
+            (Match::Near, true) => "🔵",
+            (Match::Wrong, _) => "⚫",
+        }
+    }
+
+    // How much a match reveals about a letter: Correct > Near > Wrong.
+    fn rank(&self) -> u8 {
+        match self {
+            Match::Correct => 2,
+            Match::Near => 1,
+            Match::Wrong => 0,
+        }
+    }
+}
+
+// A guess and how each of its letters matched, e.g. `["CRANE", ["wrong", "correct", ...]]`.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[schema(example = json!(["CRANE", ["wrong", "correct", "near", "wrong", "wrong"]]))]
 pub struct Guess(pub String, pub Vec<Match>);
 
 impl Guess {
     pub fn new(guess: &str, word: &str) -> Guess {
-        let mut diff = Guess(guess.to_owned(), vec![Match::Wrong; word.len()]);
-        let guess = guess.as_bytes();
-        let mut word = word.to_uppercase().as_bytes().to_owned();
+        // Letters are compared in logical (typed) order, whichever way the script runs, and
+        // final forms match their other form.
+        let mut diff = Guess(guess.to_owned(), vec![]);
+        let guess: Vec<char> = guess.chars().map(alphabet::fold).collect();
+        // Letters of the word that haven't been matched yet.
+        let mut word: Vec<Option<char>> = word
+            .to_uppercase()
+            .chars()
+            .map(|c| Some(alphabet::fold(c)))
+            .collect();
+        diff.1 = vec![Match::Wrong; word.len()];
 
         // Check correct guesses first.
-        for (i, &b) in guess.iter().enumerate() {
-            if word[i] == b {
-                word[i] = 0; // Match once
+        for (i, &c) in guess.iter().enumerate().take(word.len()) {
+            if word[i] == Some(c) {
+                word[i] = None; // Match once
                 diff.1[i] = Match::Correct;
             }
         }
 
         // Check near guesses.
-        for (i, &b) in guess.iter().enumerate() {
+        for (i, &c) in guess.iter().enumerate().take(word.len()) {
             if diff.1[i] != Match::Wrong {
                 continue;
             }
-            if let Some(j) = word.iter().position(|&x| x == b) {
-                word[j] = 0; // Match once
+            if let Some(j) = word.iter().position(|&x| x == Some(c)) {
+                word[j] = None; // Match once
                 diff.1[i] = Match::Near;
             }
         }
         diff
     }
 
+    // Describes each letter and how it matched, e.g. "A, correct position".
+    pub fn labels(&self, t: &Messages) -> Vec<String> {
+        self.0
+            .chars()
+            .zip(&self.1)
+            .map(|(letter, state)| format!("{}, {}", letter, state.description(t)))
+            .collect()
+    }
+
+    // Check the guess used the hints revealed by an earlier one: letters in the correct position
+    // are still there, and every letter found in the word is used again.
+    fn uses_hints(&self, earlier: &Guess) -> bool {
+        let letters: Vec<char> = self.0.chars().map(alphabet::fold).collect();
+        let mut unused = letters.clone();
+        for (i, (letter, state)) in earlier
+            .0
+            .chars()
+            .map(alphabet::fold)
+            .zip(&earlier.1)
+            .enumerate()
+        {
+            if *state == Match::Correct && letters.get(i) != Some(&letter) {
+                return false;
+            }
+            if *state != Match::Wrong {
+                match unused.iter().position(|&c| c == letter) {
+                    Some(j) => unused.remove(j),
+                    None => return false,
+                };
+            }
+        }
+        true
+    }
+
     // Check if the entire guess is correct.
     pub fn is_win(&self) -> bool {
         self.1.iter().all(|&x| x == Match::Correct)
     }
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+// Parse the result of a finished game from its pattern (see `Guesses::pattern`).
+pub fn parse_pattern(pattern: &str) -> Option<Vec<Vec<Match>>> {
+    let rows = pattern
+        .split('-')
+        .map(|row| row.chars().map(Match::from_code).collect())
+        .collect::<Option<Vec<Vec<Match>>>>()?;
+    let length = rows[0].len();
+    let solved = |row: &Vec<Match>| row.iter().all(|&m| m == Match::Correct);
+    // Only the last guess can be a win, and a game without one is lost after every try.
+    let (last, earlier) = rows.split_last()?;
+    match (3..=10).contains(&length)
+        && rows.iter().all(|row| row.len() == length)
+        && !earlier.iter().any(solved)
+        && (solved(last) || rows.len() == TRIES)
+        && rows.len() <= TRIES
+    {
+        true => Some(rows),
+        false => None,
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Guesses {
     pub today: String,
@@ -62,59 +190,347 @@ pub struct Guesses {
     pub max_streak: u16,
     pub streak: u16,
     pub games: u16,
+    // Generation of the saved stats, to detect concurrent updates.
+    #[serde(skip)]
+    generation: Option<u64>,
+}
+
+// The result of a finished game, to be shared, e.g. "Fastly POPs #123 4/6*" and a grid of emoji.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct Share {
+    pub text: String,
+    // The same, in high contrast colours.
+    pub contrast: String,
+    // Whether every guess used all the hints revealed before it, marked with a `*`.
+    pub hard: bool,
+    // A picture of the grid, e.g. for link previews.
+    pub image: String,
+    // A signed link to a page showing the result, which ends the text.
+    pub link: String,
+}
+
+// A finished game's result, carried in a signed share link so it can't be made up. It has the
+// pattern of the grid (see `Guesses::pattern`), never the letters.
+#[derive(Debug)]
+pub struct SharedResult {
+    pub slug: String,
+    pub number: i64,
+    pub pattern: String,
+    pub hard: bool,
+}
+
+impl SharedResult {
+    // A compact token for the result, e.g. "42.wwcwn-ccccc.1.<signature>". The game's slug is
+    // signed too, but left to the link's path.
+    pub fn sign(&self) -> Result<String, &'static str> {
+        let message = format!("{}.{}.{}", self.number, self.pattern, self.hard as u8);
+        let signature = token::sign_short(&format!("result:{}", self.slug), &message)?;
+        Ok(format!("{}.{}", message, signature))
+    }
+
+    // Check a result token was signed for a game.
+    pub fn verify(slug: &str, token: &str) -> Result<SharedResult, &'static str> {
+        let (message, signature) = token.rsplit_once('.').ok_or("Malformed token")?;
+        token::verify_short(&format!("result:{}", slug), message, signature)?;
+        match message.split('.').collect::<Vec<_>>()[..] {
+            [number, pattern, hard] => Ok(SharedResult {
+                slug: slug.to_owned(),
+                number: number.parse().map_err(|_| "Malformed token")?,
+                pattern: pattern.to_owned(),
+                hard: hard == "1",
+            }),
+            _ => Err("Malformed token"),
+        }
+    }
+}
+
+// Today's progress in a game, carried in a signed token when playing in stateless mode.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Progress {
+    #[serde(rename = "g")]
+    pub game: String,
+    #[serde(rename = "u")]
+    pub user_id: String,
+    #[serde(rename = "d")]
+    pub today: String,
+    #[serde(rename = "o")]
+    pub outcome: Vec<Guess>,
 }
 
 impl Guesses {
     // Initialize a new Guesses struct from state.
-    pub fn load(game: &str, user_id: &str, today_word_length: usize) -> Guesses {
+    pub fn load(
+        game: &str,
+        user_id: &str,
+        today_word_length: usize,
+        progress: Option<Progress>,
+    ) -> Guesses {
         // Retrieve saved stats from KV Store.
         let key = format!("{}-{}", game, user_id);
-        let saved_stats = match KVStore::open(KV_STORE_NAME) {
-            Ok(Some(stats_store)) => match stats_store.lookup_str(&key) {
-                Ok(Some(stats)) => stats,
-                _ => "".to_owned(),
-            },
-            _ => "".to_owned(),
+        let stats_store = KVStore::open(KV_STORE_NAME).ok().flatten();
+        let (saved_stats, generation) = match stats_store.as_ref().map(|s| s.lookup(&key)) {
+            Some(Ok(mut stats)) => (
+                stats.take_body().into_string(),
+                Some(stats.current_generation()),
+            ),
+            _ => ("".to_owned(), None),
         };
         // Initialize Guesses struct.
         let mut guesses: Guesses = serde_json::from_str(&saved_stats).unwrap_or_default();
+        guesses.generation = generation;
         guesses.today_length = today_word_length;
         if guesses.distribution.len() != TRIES {
             guesses.distribution = vec![0; TRIES];
         }
+        // Resume from a progress token that is more recent than the saved stats. Nothing is saved
+        // until the game is over, so replaying an older token can only rewind today's unfinished
+        // board, never a finished game.
+        let mut abandoned = false;
+        if let Some(progress) = progress {
+            if progress.game == game && progress.user_id == user_id {
+                if progress.today > guesses.today {
+                    abandoned = guesses.start_day(&progress.today);
+                }
+                if progress.today == guesses.today && progress.outcome.len() > guesses.outcome.len()
+                {
+                    guesses.outcome = progress.outcome;
+                }
+            }
+        }
         // Verify if the loaded game state is current.
         let today = utils::date_iso8601();
         if guesses.today != today {
-            // Record an abandoned session as a loss.
-            if guesses.outcome.len() > 0
-                && guesses.outcome.len() < TRIES
-                && !guesses.outcome.last().unwrap().is_win()
-            {
-                guesses.lose();
+            abandoned |= guesses.start_day(&today);
+        }
+        // Save a loss straight away: in stateless mode, it may only have been known from the
+        // player's token. The next update reloads the stats to find their new generation.
+        if abandoned {
+            if let Some(stats_store) = &stats_store {
+                guesses.insert(stats_store, &key).ok();
             }
-            guesses.today = today.to_owned();
-            guesses.outcome.clear();
+            guesses.generation = None;
         }
         guesses
     }
 
-    // Record a guess.
+    // Record a guess and save the stats, starting over if they were changed concurrently. In
+    // stateless mode, today's outcome is left to the player's progress token, and only the stats
+    // of a finished game are saved.
     pub fn update(&mut self, game: &str, user_id: &str, guess: Guess) -> Result<(), fastly::Error> {
-        // Record guess.
+        let key = format!("{}-{}", game, user_id);
+        let stats_store = KVStore::open(KV_STORE_NAME)?.unwrap();
+        if config::stateless() {
+            if self.record(guess) && self.is_over() {
+                self.insert(&stats_store, &key)?;
+            }
+            return Ok(());
+        }
+        for _ in 0..MAX_ATTEMPTS {
+            if !self.record(guess.clone()) {
+                return Ok(());
+            }
+            match self.insert(&stats_store, &key) {
+                Err(KVStoreError::ItemPreconditionFailed) => {
+                    *self = Guesses::load(game, user_id, self.today_length, None);
+                }
+                result => return Ok(result?),
+            }
+        }
+        Err(fastly::Error::msg("Could not save game stats"))
+    }
+
+    // Save the stats, unless they changed since they were loaded. In stateless mode, the stats
+    // are only saved when a game ends and may have been read from a stale copy, so the last
+    // write wins rather than failing the guess.
+    fn insert(&self, stats_store: &KVStore, key: &str) -> Result<(), KVStoreError> {
+        let insert = match (config::stateless(), self.generation) {
+            (true, _) => stats_store.build_insert(),
+            (false, Some(generation)) => stats_store.build_insert().if_generation_match(generation),
+            (false, None) => stats_store.build_insert().mode(InsertMode::Add),
+        };
+        insert.execute(key, serde_json::to_string(&self).unwrap_or_default())
+    }
+
+    // Move on to a new day, recording an abandoned game as a loss. Returns whether it was one.
+    fn start_day(&mut self, today: &str) -> bool {
+        let abandoned = !self.outcome.is_empty() && !self.is_over();
+        if abandoned {
+            self.lose();
+        }
+        self.today = today.to_owned();
+        self.outcome.clear();
+        abandoned
+    }
+
+    // Record a guess, unless today's game is already over.
+    fn record(&mut self, guess: Guess) -> bool {
+        if self.is_over() {
+            return false;
+        }
         self.outcome.push(guess);
         // Update outcome.
         if self.outcome.last().unwrap().is_win() {
             self.win();
-        } else if self.outcome.len() == TRIES {
+        } else if self.outcome.len() >= TRIES {
             self.lose();
         }
-        // Save new game stats.
-        let mut stats_store = KVStore::open(KV_STORE_NAME)?.unwrap();
-        stats_store.insert(
-            &format!("{}-{}", game, user_id),
-            serde_json::to_string(&self)?,
-        )?;
-        Ok(())
+        true
+    }
+
+    // Today's progress, to be signed and handed back to the player.
+    pub fn progress(&self, game: &str, user_id: &str) -> Progress {
+        Progress {
+            game: game.to_owned(),
+            user_id: user_id.to_owned(),
+            today: self.today.to_owned(),
+            outcome: self.outcome.clone(),
+        }
+    }
+
+    // Check if today's game has been won.
+    pub fn won_today(&self) -> bool {
+        self.today == self.last_win
+    }
+
+    // The percentage of games won.
+    pub fn win_rate(&self) -> u16 {
+        match self.games {
+            0 => 0,
+            // Widened, so long records don't overflow.
+            games => {
+                (self.distribution.iter().map(|&n| u32::from(n)).sum::<u32>() * 100
+                    / u32::from(games)) as u16
+            }
+        }
+    }
+
+    // Check if today's game has been won or lost.
+    pub fn is_over(&self) -> bool {
+        self.today == self.last_win || self.today == self.last_loss
+    }
+
+    // Check every guess used all the hints revealed by the guesses before it. A single guess
+    // doesn't count.
+    pub fn is_hard_mode(&self) -> bool {
+        self.outcome.len() > 1
+            && self.outcome.iter().enumerate().all(|(i, guess)| {
+                self.outcome[..i]
+                    .iter()
+                    .all(|earlier| guess.uses_hints(earlier))
+            })
+    }
+
+    // The result of today's game once it's over, to be shared with a link to the game. Rows are
+    // mirrored for right-to-left games, to look like the board.
+    pub fn share(&self, game: &GameData, number: i64, url: &str) -> Option<Share> {
+        if !self.is_over() {
+            return None;
+        }
+        let hard = self.is_hard_mode();
+        // Fall back to the game's URL and cover card if the result can't be signed.
+        let (link, image) = match (SharedResult {
+            slug: game.slug.to_owned(),
+            number,
+            pattern: self.pattern(),
+            hard,
+        })
+        .sign()
+        {
+            Ok(token) => (
+                format!("{}/result/{}", url, token),
+                format!("{}/result/{}/grid.svg", url, token),
+            ),
+            Err(_) => (url.to_owned(), format!("{}/card.svg", url)),
+        };
+        Some(Share {
+            text: self.share_text(game, number, &link, false),
+            contrast: self.share_text(game, number, &link, true),
+            hard,
+            image,
+            link,
+        })
+    }
+
+    // The text of today's result: a heading like "Fastly POPs #123 4/6*", a row of emoji per
+    // guess and the link, in the normal or high contrast colours.
+    fn share_text(&self, game: &GameData, number: i64, link: &str, contrast: bool) -> String {
+        let score = match self.won_today() {
+            true => self.outcome.len().to_string(),
+            false => "X".to_owned(),
+        };
+        let mut lines = vec![format!(
+            "{} #{} {}/{}{}",
+            game.game,
+            number,
+            score,
+            TRIES,
+            if self.is_hard_mode() { "*" } else { "" }
+        )];
+        for Guess(_, result) in &self.outcome {
+            let mut row: Vec<&str> = result.iter().map(|m| m.emoji(contrast)).collect();
+            if game.direction == Direction::Rtl {
+                row.reverse();
+            }
+            lines.push(row.join(" "));
+        }
+        lines.push(link.to_owned());
+        lines.join("\n")
+    }
+
+    // Today's result without the letters, e.g. "wwcwn-ccccc": a row per guess, and a letter per
+    // tile for correct, near and wrong.
+    pub fn pattern(&self) -> String {
+        self.outcome
+            .iter()
+            .map(|Guess(_, result)| result.iter().map(Match::code).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    // Summarises the last guess and the game's outcome, for screen readers.
+    pub fn summary(&self, t: &Messages) -> String {
+        let guess = match self.outcome.last() {
+            Some(guess) => guess,
+            None => return String::new(),
+        };
+        let mut summary = i18n::fill(
+            t.summary,
+            [
+                self.outcome.len().to_string(),
+                TRIES.to_string(),
+                guess.0.to_owned(),
+                guess.labels(t).join("; "),
+            ],
+        );
+        if guess.is_win() {
+            summary = format!("{} {}", summary, t.summary_win);
+        } else if self.outcome.len() == TRIES {
+            summary = format!("{} {}", summary, t.summary_lose);
+        }
+        summary
+    }
+
+    // The best state of each letter guessed today, for the keyboard. Letters with a final form
+    // share their state with it.
+    pub fn letter_states(&self) -> BTreeMap<String, Match> {
+        let mut states = BTreeMap::new();
+        for Guess(word, result) in &self.outcome {
+            for (letter, &state) in word.chars().map(alphabet::fold).zip(result) {
+                let best = states.entry(letter).or_insert(state);
+                if state.rank() > best.rank() {
+                    *best = state;
+                }
+            }
+        }
+        states
+            .into_iter()
+            .flat_map(|(letter, state)| {
+                alphabet::final_form(letter)
+                    .into_iter()
+                    .chain([letter])
+                    .map(move |letter| (letter.to_string(), state))
+            })
+            .collect()
     }
 
     // Update win statistics.
@@ -136,72 +552,193 @@ impl Guesses {
     }
 }
 
-impl Display for Guess {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        write!(f, "<section class=\"row\">")?;
-        for i in 0..self.1.len() {
-            write!(
-                f,
-                "<div class=\"tile\" data-state=\"{}\">{}</div>",
-                match self.1[i] {
-                    Match::Correct => "correct",
-                    Match::Near => "near",
-                    Match::Wrong => "wrong",
-                },
-                self.0.as_bytes()[i] as char
-            )?;
-        }
-        write!(f, "</section>")
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Match::{Correct, Near, Wrong};
+
+    #[test]
+    fn guess_matches_letters() {
+        assert_eq!(
+            Guess::new("CRANE", "crate").1,
+            vec![Correct, Correct, Correct, Wrong, Correct]
+        );
     }
-}
 
-impl Display for Guesses {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        let win_rate = match &self.games {
-            0 => 0,
-            _ => &self.distribution.iter().sum() / &self.games * 100,
+    #[test]
+    fn guess_matches_repeated_letters_once() {
+        // The E in the correct position is matched first, leaving one E for the others.
+        assert_eq!(
+            Guess::new("EERIE", "there").1,
+            vec![Near, Wrong, Near, Wrong, Correct]
+        );
+        assert_eq!(
+            Guess::new("SPEED", "abide").1,
+            vec![Wrong, Wrong, Near, Wrong, Near]
+        );
+    }
+
+    #[test]
+    fn guess_matches_final_forms() {
+        let guess = Guess::new("שלומ", "שלום");
+        assert_eq!(guess.1, vec![Correct; 4]);
+        // The guess is kept as typed.
+        assert_eq!(guess.0, "שלומ");
+        assert_eq!(Guess::new("ימם", "מים").1, vec![Near, Near, Correct]);
+        assert_eq!(Guess::new("ΛΟΓΟΣ", "λογος").1, vec![Correct; 5]);
+    }
+
+    #[test]
+    fn guess_matches_right_to_left_words_in_typed_order() {
+        assert_eq!(Guess::new("םולש", "שלום").1, vec![Near; 4]);
+    }
+
+    #[test]
+    fn uses_hints_keeps_correct_letters_in_place() {
+        let earlier = Guess::new("CRATE", "crane");
+        assert!(Guess::new("CRANE", "crane").uses_hints(&earlier));
+        assert!(!Guess::new("CARVE", "crane").uses_hints(&earlier));
+        // Final forms count as the same letter.
+        let earlier = Guess::new("שלומ", "שלום");
+        assert!(Guess::new("שלום", "שלום").uses_hints(&earlier));
+    }
+
+    #[test]
+    fn uses_hints_uses_every_letter_found() {
+        // N and E are in the word, but not where they were guessed.
+        let earlier = Guess::new("NOTES", "crane");
+        assert!(Guess::new("ENACT", "crane").uses_hints(&earlier));
+        assert!(!Guess::new("CLAMP", "crane").uses_hints(&earlier));
+        assert!(!Guess::new("NAILS", "crane").uses_hints(&earlier));
+    }
+
+    #[test]
+    fn hard_mode_needs_every_guess_to_use_the_hints() {
+        assert!(played(&["CRATE", "CRANE"], "crane").is_hard_mode());
+        assert!(!played(&["CRATE", "SLOTH", "CRANE"], "crane").is_hard_mode());
+    }
+
+    #[test]
+    fn pattern_has_a_row_per_guess() {
+        assert_eq!(
+            played(&["NOTES", "CRATE", "CRANE"], "crane").pattern(),
+            "nwwnw-cccwc-ccccc"
+        );
+    }
+
+    #[test]
+    fn share_text_scores_wins() {
+        let guesses = played(&["CRATE", "CRANE"], "crane");
+        let game = game("Fastly POPs", Direction::Ltr);
+        assert_eq!(
+            guesses.share_text(&game, 123, "https://yourdle/pops", false),
+            "Fastly POPs #123 2/6*\n🟢 🟢 🟢 ⚫ 🟢\n🟢 🟢 🟢 🟢 🟢\nhttps://yourdle/pops"
+        );
+        assert_eq!(
+            guesses.share_text(&game, 123, "https://yourdle/pops", true),
+            "Fastly POPs #123 2/6*\n🟠 🟠 🟠 ⚫ 🟠\n🟠 🟠 🟠 🟠 🟠\nhttps://yourdle/pops"
+        );
+        // Not hard mode: SLOTH leaves out the letters CRATE found.
+        let guesses = played(&["CRATE", "SLOTH", "CRANE"], "crane");
+        assert!(guesses
+            .share_text(&game, 123, "", false)
+            .starts_with("Fastly POPs #123 3/6\n"));
+    }
+
+    #[test]
+    fn share_text_scores_losses_as_x() {
+        let guesses = played(
+            &["NOTES", "CRATE", "TRACE", "SLOTH", "CRATE", "TRACE"],
+            "crane",
+        );
+        assert!(guesses.is_over() && !guesses.won_today());
+        assert!(guesses
+            .share_text(&game("Fastly POPs", Direction::Ltr), 7, "", false)
+            .starts_with("Fastly POPs #7 X/6\n"));
+    }
+
+    #[test]
+    fn share_text_mirrors_right_to_left_rows() {
+        let guesses = played(&["לשום", "שלום"], "שלום");
+        assert_eq!(
+            guesses.share_text(&game("שלום", Direction::Rtl), 1, "", false),
+            "שלום #1 2/6*\n🟢 🟢 🟡 🟡\n🟢 🟢 🟢 🟢\n"
+        );
+    }
+
+    #[test]
+    fn letter_states_keep_the_best_state() {
+        // N is near, then correct; E is correct, then near.
+        let states = played(&["NOTES", "CANOE", "SPENT"], "crane").letter_states();
+        assert_eq!(states["N"], Correct);
+        assert_eq!(states["E"], Correct);
+        assert_eq!(states["A"], Near);
+        assert_eq!(states["O"], Wrong);
+        assert_eq!(states.len(), 8);
+    }
+
+    #[test]
+    fn letter_states_share_final_forms() {
+        let states = played(&["ימם"], "מים").letter_states();
+        assert_eq!(states["מ"], Correct);
+        assert_eq!(states["ם"], Correct);
+        assert_eq!(states["י"], Near);
+    }
+
+    #[test]
+    fn parse_pattern_reads_finished_games() {
+        assert_eq!(
+            parse_pattern("wwcwn-ccccc"),
+            Some(vec![
+                vec![Wrong, Wrong, Correct, Wrong, Near],
+                vec![Correct; 5]
+            ])
+        );
+        // A loss needs every try.
+        assert_eq!(
+            parse_pattern("wwwww-wwwww-wwwww-wwwww-wwwww-wwwww").map(|rows| rows.len()),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn parse_pattern_rejects_impossible_games() {
+        for pattern in [
+            // Unfinished.
+            "wwwww",
+            // Solved before the last row.
+            "ccccc-ccccc",
+            // Too many rows.
+            "wwwww-wwwww-wwwww-wwwww-wwwww-wwwww-ccccc",
+            // Too short, or rows of different lengths.
+            "cc",
+            "wwwww-cccc",
+            // Unknown states.
+            "wwwwx-ccccc",
+            "",
+        ] {
+            assert_eq!(parse_pattern(pattern), None, "{}", pattern);
+        }
+    }
+
+    // Today's game after each of the guesses, against the answer.
+    fn played(words: &[&str], answer: &str) -> Guesses {
+        let mut guesses = Guesses {
+            today: "2024-01-01".to_owned(),
+            today_length: answer.chars().count(),
+            distribution: vec![0; TRIES],
+            ..Default::default()
         };
-        // TODO: Use a templating engine.
-        write!(
-            f,
-            "{}",
-            include_str!("browser/stats.html")
-                .replace("{GAMES}", &self.games.to_string())
-                .replace("{STREAK}", &self.streak.to_string())
-                .replace("{MAX_STREAK}", &self.max_streak.to_string())
-                .replace("{WON_TODAY}", &(&self.last_win == &self.today).to_string())
-                .replace("{PERC_WON}", &win_rate.to_string())
-        )?;
-        for i in 0..TRIES {
-            write!(
-                f,
-                "<div class=\"dist\">\
-                <div class=\"bar\"><span>{}</span></div>\
-                <h5>{}</h5>\
-                </div>",
-                &self.distribution[i].to_string(),
-                i + 1
-            )?;
-        }
-        write!(f, "</div></div>")?;
-        for i in 0..TRIES {
-            if i < self.outcome.len() {
-                write!(f, "{}", &self.outcome[i])?;
-            } else {
-                if i == self.outcome.len()
-                    && self.today != self.last_loss
-                    && self.today != self.last_win
-                {
-                    write!(f, "<section class=\"row active\">")?;
-                } else {
-                    write!(f, "<section class=\"row\">")?;
-                }
-                for _ in 0..self.today_length {
-                    write!(f, "<div class=\"tile\"></div>")?;
-                }
-                write!(f, "</section>")?;
-            }
+        for word in words {
+            guesses.record(Guess::new(word, answer));
         }
-        write!(f, "<!-- guesses end -->")
+        guesses
+    }
+
+    fn game(name: &str, direction: Direction) -> GameData {
+        let mut game = GameData::default();
+        game.game = name.to_owned();
+        game.direction = direction;
+        game
     }
 }
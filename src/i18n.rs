@@ -0,0 +1,275 @@
+use fastly::Request;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+mod de;
+mod en;
+mod es;
+mod fr;
+
+// Languages the pages can be shown in.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+}
+
+// The text of the game, shared result and new game pages. Messages can have numbered placeholders, filled in
+// with `fill`, and the ones marked as HTML are rendered as they are.
+pub struct Messages {
+    pub lang: &'static str,
+    // Game page.
+    pub made_with: &'static str,
+    pub how_to_play: &'static str,
+    pub statistics: &'static str,
+    pub close: &'static str,
+    pub rules: &'static str,
+    pub example_correct: &'static str,
+    pub example_near: &'static str,
+    pub example_wrong: &'static str,
+    pub number: &'static str,
+    pub contrast: &'static str,
+    pub create_your_own: &'static str,
+    pub feedback: &'static str,
+    pub feedback_intro: &'static str,
+    pub feedback_greeting: &'static str,
+    pub feedback_placeholder: &'static str,
+    pub send: &'static str,
+    pub games: &'static str,
+    pub won: &'static str,
+    pub streak: &'static str,
+    pub best_streak: &'static str,
+    pub share: &'static str,
+    pub distribution: &'static str,
+    pub board: &'static str,
+    // {0}: the number of the guess.
+    pub guess_number: &'static str,
+    pub your_guess: &'static str,
+    pub guess: &'static str,
+    pub keyboard: &'static str,
+    pub enter: &'static str,
+    pub backspace: &'static str,
+    // {0}: the name of the game.
+    pub play: &'static str,
+    // HTML.
+    pub embed_footer: &'static str,
+    // HTML.
+    pub footer: &'static str,
+    // {0}: the number of the guess, {1}: guesses allowed, {2}: the guess, {3}: its letters.
+    pub summary: &'static str,
+    pub summary_win: &'static str,
+    pub summary_lose: &'static str,
+    pub game_over: &'static str,
+    // {0}: the length of today's word.
+    pub wrong_length: &'static str,
+    // Shared result page.
+    // {0}: the name of the game, {1}: the puzzle number, {2}: the score, e.g. "3/6".
+    pub solved: &'static str,
+    // {0}: the name of the game, {1}: the puzzle number.
+    pub not_solved: &'static str,
+    pub hard_mode: &'static str,
+    pub verified: &'static str,
+    // New game page.
+    pub new_game: &'static str,
+    pub tagline: &'static str,
+    pub new_description: &'static str,
+    pub no_script: &'static str,
+    pub make_your_own: &'static str,
+    pub new_rules: &'static str,
+    pub new_rules_daily: &'static str,
+    pub new_intro: &'static str,
+    pub name_label: &'static str,
+    pub name_placeholder: &'static str,
+    pub description_label: &'static str,
+    pub description_placeholder: &'static str,
+    pub words_label: &'static str,
+    pub words_placeholder: &'static str,
+    pub too_few_words: &'static str,
+    // HTML.
+    pub ai_hint: &'static str,
+    pub match_words: &'static str,
+    pub cyrillic: &'static str,
+    pub greek: &'static str,
+    pub hebrew: &'static str,
+    pub arabic: &'static str,
+    pub direction: &'static str,
+    pub ltr: &'static str,
+    pub rtl: &'static str,
+    pub language: &'static str,
+    pub player_language: &'static str,
+    pub origins_label: &'static str,
+    pub origins_placeholder: &'static str,
+    pub make_it: &'static str,
+    pub ready: &'static str,
+    pub created: &'static str,
+    pub share_link: &'static str,
+    pub embed_snippet: &'static str,
+    pub copy_snippet: &'static str,
+    pub make_another: &'static str,
+    // HTML.
+    pub built_by: &'static str,
+    // Used by the browser scripts as well.
+    pub client: ClientMessages,
+}
+
+// The text the browser scripts need, handed to them as JSON.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientMessages {
+    pub correct: &'static str,
+    pub near: &'static str,
+    pub wrong: &'static str,
+    pub you_win: &'static str,
+    pub better_luck: &'static str,
+    pub thanks: &'static str,
+    pub try_later: &'static str,
+    pub copied: &'static str,
+    pub copy: &'static str,
+    // {0}: the name of the game, {1}: its URL.
+    pub check_out: &'static str,
+    pub not_on_list: &'static str,
+    pub slow_down: &'static str,
+    pub went_wrong: &'static str,
+    pub enough_games: &'static str,
+    // {0}: the name of the game, {1}: its URL.
+    pub made_game: &'static str,
+    pub too_short: &'static str,
+    pub game_exists: &'static str,
+}
+
+impl Locale {
+    const ALL: [Locale; 4] = [Locale::En, Locale::De, Locale::Fr, Locale::Es];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+            Locale::Es => "es",
+        }
+    }
+
+    pub fn messages(&self) -> &'static Messages {
+        match self {
+            Locale::En => &en::MESSAGES,
+            Locale::De => &de::MESSAGES,
+            Locale::Fr => &fr::MESSAGES,
+            Locale::Es => &es::MESSAGES,
+        }
+    }
+
+    // The locale a game is set to or, failing that, the best match for the request's
+    // `Accept-Language` header.
+    pub fn negotiate(req: &Request, locale: Option<Locale>) -> Locale {
+        locale.unwrap_or_else(|| {
+            Locale::from_accept_language(req.get_header_str("accept-language").unwrap_or_default())
+        })
+    }
+
+    // The best match for an `Accept-Language` header, or English if none match.
+    fn from_accept_language(header: &str) -> Locale {
+        let mut languages: Vec<(&str, f32)> = header
+            .split(',')
+            .map(|language| {
+                let mut parts = language.split(';').map(str::trim);
+                let tag = parts.next().unwrap_or_default();
+                let quality = parts
+                    .find_map(|param| param.strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (tag, quality)
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect();
+        // A stable sort keeps the header's order for languages of the same quality.
+        languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+        languages
+            .into_iter()
+            .find_map(|(tag, _)| {
+                let primary = tag.split('-').next().unwrap_or_default();
+                Locale::ALL
+                    .iter()
+                    .copied()
+                    .find(|locale| primary.eq_ignore_ascii_case(locale.as_str()))
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Messages {
+    // The browser scripts' messages, to be put in the page.
+    pub fn client_json(&self) -> String {
+        serde_json::to_string(&self.client).unwrap_or_default()
+    }
+}
+
+// Fill in a message's numbered placeholders, e.g. "Guess {0}", in a single pass so placeholders
+// in the arguments are left as they are.
+pub fn fill<T: ToString>(message: &str, args: impl IntoIterator<Item = T>) -> String {
+    let args: Vec<String> = args.into_iter().map(|arg| arg.to_string()).collect();
+    let mut filled = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let placeholder = rest[start + 1..].split_once('}').and_then(|(index, _)| {
+            let arg = args.get(index.parse::<usize>().ok()?)?;
+            Some((arg, index.len() + 2))
+        });
+        match placeholder {
+            Some((arg, len)) => {
+                filled.push_str(arg);
+                rest = &rest[start + len..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_accept_language_matches_primary_tags() {
+        assert_eq!(Locale::from_accept_language("de-CH, en;q=0.5"), Locale::De);
+        assert_eq!(Locale::from_accept_language("FR-ca"), Locale::Fr);
+    }
+
+    #[test]
+    fn from_accept_language_prefers_higher_quality() {
+        assert_eq!(
+            Locale::from_accept_language("en-GB;q=0.2, fr;q=0.9"),
+            Locale::Fr
+        );
+        assert_eq!(Locale::from_accept_language("es;q=0, de;q=0.1"), Locale::De);
+        assert_eq!(Locale::from_accept_language("es, de"), Locale::Es);
+    }
+
+    #[test]
+    fn from_accept_language_defaults_to_english() {
+        assert_eq!(Locale::from_accept_language(""), Locale::En);
+        assert_eq!(Locale::from_accept_language("pt, *"), Locale::En);
+        assert_eq!(Locale::from_accept_language("de;q=0"), Locale::En);
+    }
+
+    #[test]
+    fn fill_replaces_numbered_placeholders() {
+        assert_eq!(fill("Guess {0} of {1}", [3, 6]), "Guess 3 of 6");
+        assert_eq!(fill("{1}, {0}", ["a", "b"]), "b, a");
+    }
+
+    #[test]
+    fn fill_leaves_other_braces_alone() {
+        assert_eq!(fill("{0}", ["{1}"]), "{1}");
+        assert_eq!(fill("{2} {x} {", ["a"]), "{2} {x} {");
+    }
+}
@@ -0,0 +1,101 @@
+use super::{ClientMessages, Messages};
+
+pub static MESSAGES: Messages = Messages {
+    lang: "de",
+    made_with: "Gemacht mit Yourdle - Erstelle dein eigenes Wortspiel",
+    how_to_play: "Spielanleitung",
+    statistics: "Statistik",
+    close: "Schließen",
+    rules: "Du hast 6 Versuche. Nach jedem Versuch zeigt die Farbe der Kacheln, wie nah du dem Wort gekommen bist.",
+    example_correct: "Der Buchstabe L ist an der richtigen Stelle.",
+    example_near: "Der Buchstabe M ist an der falschen Stelle.",
+    example_wrong: "Der Buchstabe O kommt im Wort nicht vor.",
+    number: "Nr.",
+    contrast: "Hohen Kontrast umschalten",
+    create_your_own: "Eigenes Spiel erstellen",
+    feedback: "Feedback",
+    feedback_intro: "Nimm dir einen Moment Zeit und teile deine Gedanken mit mir – gemeinsam machen wir Yourdle zu einem noch besseren Wortabenteuer!",
+    feedback_greeting: "Hallo Dora,",
+    feedback_placeholder: "Das denke ich... (10-140 Zeichen)",
+    send: "Senden",
+    games: "Spiele",
+    won: "Gewonnen",
+    streak: "Serie",
+    best_streak: "Beste Serie",
+    share: "Teilen 💪",
+    distribution: "Verteilung der Versuche",
+    board: "Versuche",
+    guess_number: "Versuch {0}",
+    your_guess: "Dein Versuch",
+    guess: "Raten",
+    keyboard: "Tastatur",
+    enter: "Eingabe",
+    backspace: "Löschen",
+    play: "{0} spielen",
+    embed_footer: r#"oder <a href="/new" target="_blank">erstelle dein eigenes</a> Wortspiel mit Yourdle 🚀"#,
+    footer: r##"Gib <a id="showFeedback" href="#">Feedback</a>, <a id="shareThis" href="#">teile das Spiel</a> oder <a href="/new" class="diy" target="_self">erstelle dein eigenes</a> Wortspiel 🚀"##,
+    summary: "Versuch {0} von {1}, {2}: {3}.",
+    summary_win: "Gewonnen!",
+    summary_lose: "Keine Versuche mehr.",
+    game_over: "Das heutige Spiel ist vorbei",
+    wrong_length: "Wörter müssen {0} Buchstaben haben",
+    solved: "Jemand hat {0} #{1} mit {2} gelöst!",
+    not_solved: "Jemand hat {0} #{1} diesmal nicht gelöst.",
+    hard_mode: "Im schweren Modus: Jeder Versuch hat alle vorher aufgedeckten Hinweise genutzt.",
+    verified: "Dieses Ergebnis ist von Yourdle signiert, also echt. Die Buchstaben bleiben verborgen, bis du selbst spielst.",
+    new_game: "Neues Spiel",
+    tagline: "Yourdle - Erstelle dein eigenes Wortspiel",
+    new_description: "Erstelle dein eigenes Wortspiel. Fordere deine Freunde mit cleveren Rätseln heraus. Tauche ein in knifflige Denkspiele und teile den Spaß an Wörtern!",
+    no_script: "Aktiviere JavaScript, um diese App zu nutzen.",
+    make_your_own: "Erstelle dein eigenes Wortspiel",
+    new_rules: "Lade eine Wortliste hoch und erstelle ein neues Spiel. Teile den Link mit deinen Freunden.",
+    new_rules_daily: "Jeden Tag wird ein zufälliges Wort aus der Liste gewählt. Du hast 6 Versuche, um es zu erraten. Die Farben der Kacheln zeigen, wie gut jeder Versuch war.",
+    new_intro: "Erstelle dein eigenes Wortspiel. Fordere deine Freunde heraus.",
+    name_label: "Name des Spiels",
+    name_placeholder: "Eingängig, 3-12 Zeichen",
+    description_label: "Beschreibung",
+    description_placeholder: "Stelle das Spiel in höchstens 140 Zeichen vor",
+    words_label: "Wortliste",
+    words_placeholder: "7-365 Wörter, 3-10 Buchstaben pro Wort",
+    too_few_words: "Wir brauchen mindestens 7 Wörter",
+    ai_hint: r#"<a href="https://chat.openai.com" target="_blank">Frag eine KI</a> nach einer Wortliste, z. B. <span>„Nenne mir 100 Gewürze, jeweils ein Wort mit weniger als 10 Buchstaben“</span>"#,
+    match_words: "Passend zur Wortliste",
+    cyrillic: "ЙЦУКЕН (Kyrillisch)",
+    greek: "Griechisch",
+    hebrew: "Hebräisch",
+    arabic: "Arabisch",
+    direction: "Schreibrichtung",
+    ltr: "Von links nach rechts",
+    rtl: "Von rechts nach links",
+    language: "Sprache",
+    player_language: "Die Sprache der Spieler",
+    origins_label: "Auf anderen Websites einbetten (optional)",
+    origins_placeholder: "z. B. https://intranet.example.com",
+    make_it: "Erstellen ✨",
+    ready: "Bereit zum Spielen",
+    created: "Glückwunsch, du hast dein eigenes Wortspiel erstellt! Teile diesen Link mit deinen Freunden (oder klicke, um gleich zu spielen):",
+    share_link: "Teilen 🔗",
+    embed_snippet: "Bette es mit diesem Code auf deiner Website ein:",
+    copy_snippet: "Code kopieren 📋",
+    make_another: "Noch eins erstellen",
+    built_by: r#"Gebaut von <a href="https://doramilitaru.com" target="_blank">Dora</a> 👩🏻‍🔧 mit <a href="https://www.fastly.com/products/edge-compute" target="_blank">Fastly Compute@Edge</a> 🚀 &amp; <a href="https://rust-lang.org/" target="_blank">Rust</a> 🦀"#,
+    client: ClientMessages {
+        correct: "richtige Stelle",
+        near: "im Wort, falsche Stelle",
+        wrong: "nicht im Wort",
+        you_win: "Gewonnen ❤️",
+        better_luck: "Nächstes Mal klappt's 😓",
+        thanks: "Danke!",
+        try_later: "Etwas ist schiefgelaufen 🥲\nVersuch es später noch einmal",
+        copied: "In die Zwischenablage kopiert\n📋",
+        copy: "Kopieren:",
+        check_out: "Schau dir dieses lustige Wortspiel an, {0}: {1}",
+        not_on_list: "Das steht nicht auf der Liste",
+        slow_down: "Nicht so schnell ⏳\nVersuch es gleich noch einmal",
+        went_wrong: "Etwas ist schiefgelaufen",
+        enough_games: "Genug Spiele für heute 🥲\nVersuch es morgen wieder",
+        made_game: "Ich habe ein Wortspiel gemacht! Schau dir „{0}“ an: {1}",
+        too_short: "Zu kurz",
+        game_exists: "Dieses Spiel gibt es schon",
+    },
+};
@@ -0,0 +1,101 @@
+use super::{ClientMessages, Messages};
+
+pub static MESSAGES: Messages = Messages {
+    lang: "en",
+    made_with: "Made with Yourdle - Create your own word game",
+    how_to_play: "How to play",
+    statistics: "Game statistics",
+    close: "Close",
+    rules: "You have 6 guesses. After each attempt, the color of the tiles will change to show how close your guess was.",
+    example_correct: "The letter L is in the correct spot.",
+    example_near: "The letter M is in the wrong spot.",
+    example_wrong: "The letter O is not in the word.",
+    number: "No.",
+    contrast: "Toggle high contrast mode",
+    create_your_own: "Create your own",
+    feedback: "Feedback",
+    feedback_intro: "Please take a moment to share your thoughts with me, and together, let's make Yourdle an even better word adventure!",
+    feedback_greeting: "Hey Dora,",
+    feedback_placeholder: "Here's what I think... (10-140 chars)",
+    send: "Send",
+    games: "Games",
+    won: "Won",
+    streak: "Streak",
+    best_streak: "Best streak",
+    share: "Share 💪",
+    distribution: "Guess distribution",
+    board: "Guesses",
+    guess_number: "Guess {0}",
+    your_guess: "Your guess",
+    guess: "Guess",
+    keyboard: "Keyboard",
+    enter: "Enter",
+    backspace: "Backspace",
+    play: "Play {0}",
+    embed_footer: r#"or <a href="/new" target="_blank">make your own</a> word game on Yourdle 🚀"#,
+    footer: r##"Give <a id="showFeedback" href="#">feedback</a>, <a id="shareThis" href="#">share this</a> or <a href="/new" class="diy" target="_self">make your own</a> word game 🚀"##,
+    summary: "Guess {0} of {1}, {2}: {3}.",
+    summary_win: "You win!",
+    summary_lose: "No guesses left.",
+    game_over: "Today's game is over",
+    wrong_length: "Guesses need {0} letters",
+    solved: "Someone solved {0} #{1} in {2}!",
+    not_solved: "Someone didn't solve {0} #{1} this time.",
+    hard_mode: "In hard mode: every guess used all the hints revealed before it.",
+    verified: "This result is signed by Yourdle, so it's the real thing. The letters stay hidden until you play.",
+    new_game: "New game",
+    tagline: "Yourdle - Create your own word game",
+    new_description: "Create your own word game. Challenge friends with clever puzzles. Dive into brain-teasing fun and share the excitement of word mastery!",
+    no_script: "You need to enable JavaScript to run this app.",
+    make_your_own: "Make your own word game",
+    new_rules: "Upload a word list and create a new game. Share the link with your friends.",
+    new_rules_daily: "Every day, a random word is selected from the list. You get 6 guesses to solve the challenge. Tile colors change based on the accuracy of each guess.",
+    new_intro: "Create your own word game. Challenge your friends.",
+    name_label: "Name your game",
+    name_placeholder: "A catchy 3-12 chars",
+    description_label: "Describe it",
+    description_placeholder: "Introduce the game in 140 chars max",
+    words_label: "Word list",
+    words_placeholder: "7-365 words, 3-10 letters per word",
+    too_few_words: "We need at least 7 words",
+    ai_hint: r#"<a href="https://chat.openai.com" target="_blank">Ask an AI</a> to generate a list of words for you, e.g., <span>"Give me a list of 100 one-word culinary spices, each with under 10 letters"</span>"#,
+    match_words: "Match the word list",
+    cyrillic: "ЙЦУКЕН (Cyrillic)",
+    greek: "Greek",
+    hebrew: "Hebrew",
+    arabic: "Arabic",
+    direction: "Writing direction",
+    ltr: "Left to right",
+    rtl: "Right to left",
+    language: "Language",
+    player_language: "The player's language",
+    origins_label: "Embed on other sites (optional)",
+    origins_placeholder: "e.g., https://intranet.example.com",
+    make_it: "Make it ✨",
+    ready: "Ready to play",
+    created: "Congratulations, you've created your own word game! Share this link with your friends (or click to play now):",
+    share_link: "Share 🔗",
+    embed_snippet: "Embed it on your site with this snippet:",
+    copy_snippet: "Copy snippet 📋",
+    make_another: "Make another",
+    built_by: r#"Built by <a href="https://doramilitaru.com" target="_blank">Dora</a> 👩🏻‍🔧 with <a href="https://www.fastly.com/products/edge-compute" target="_blank">Fastly Compute@Edge</a> 🚀 &amp; <a href="https://rust-lang.org/" target="_blank">Rust</a> 🦀"#,
+    client: ClientMessages {
+        correct: "correct position",
+        near: "in the word, wrong position",
+        wrong: "not in the word",
+        you_win: "You win ❤️",
+        better_luck: "Better luck next time 😓",
+        thanks: "Thank you!",
+        try_later: "Something went wrong 🥲\nTry again later",
+        copied: "Copied to clipboard\n📋",
+        copy: "Copy:",
+        check_out: "Check out this fun word game, {0}: {1}",
+        not_on_list: "That's not on the list",
+        slow_down: "Slow down ⏳\nTry again in a bit",
+        went_wrong: "Something went wrong",
+        enough_games: "That's enough games for today 🥲\nTry again tomorrow",
+        made_game: "I made a word game! Check out \"{0}\" at: {1}",
+        too_short: "Too short",
+        game_exists: "That game already exists",
+    },
+};
@@ -0,0 +1,101 @@
+use super::{ClientMessages, Messages};
+
+pub static MESSAGES: Messages = Messages {
+    lang: "es",
+    made_with: "Hecho con Yourdle - Crea tu propio juego de palabras",
+    how_to_play: "Cómo jugar",
+    statistics: "Estadísticas",
+    close: "Cerrar",
+    rules: "Tienes 6 intentos. Después de cada intento, el color de las casillas cambia para mostrar lo cerca que estás.",
+    example_correct: "La letra L está en el lugar correcto.",
+    example_near: "La letra M está en el lugar equivocado.",
+    example_wrong: "La letra O no está en la palabra.",
+    number: "N.º",
+    contrast: "Activar o desactivar el alto contraste",
+    create_your_own: "Crea el tuyo",
+    feedback: "Comentarios",
+    feedback_intro: "Tómate un momento para contarme qué te parece y, juntos, ¡hagamos de Yourdle una aventura de palabras aún mejor!",
+    feedback_greeting: "Hola, Dora:",
+    feedback_placeholder: "Esto es lo que pienso... (10-140 caracteres)",
+    send: "Enviar",
+    games: "Partidas",
+    won: "Ganadas",
+    streak: "Racha",
+    best_streak: "Mejor racha",
+    share: "Compartir 💪",
+    distribution: "Distribución de intentos",
+    board: "Intentos",
+    guess_number: "Intento {0}",
+    your_guess: "Tu intento",
+    guess: "Probar",
+    keyboard: "Teclado",
+    enter: "Intro",
+    backspace: "Borrar",
+    play: "Juega a {0}",
+    embed_footer: r#"o <a href="/new" target="_blank">crea tu propio</a> juego de palabras en Yourdle 🚀"#,
+    footer: r##"Deja tus <a id="showFeedback" href="#">comentarios</a>, <a id="shareThis" href="#">compártelo</a> o <a href="/new" class="diy" target="_self">crea tu propio</a> juego de palabras 🚀"##,
+    summary: "Intento {0} de {1}, {2}: {3}.",
+    summary_win: "¡Has ganado!",
+    summary_lose: "No quedan intentos.",
+    game_over: "La partida de hoy ha terminado",
+    wrong_length: "Las palabras deben tener {0} letras",
+    solved: "¡Alguien resolvió {0} n.º {1} en {2}!",
+    not_solved: "Alguien no resolvió {0} n.º {1} esta vez.",
+    hard_mode: "En modo difícil: cada intento usó todas las pistas reveladas antes.",
+    verified: "Este resultado está firmado por Yourdle, así que es auténtico. Las letras siguen ocultas hasta que juegues.",
+    new_game: "Nuevo juego",
+    tagline: "Yourdle - Crea tu propio juego de palabras",
+    new_description: "Crea tu propio juego de palabras. Reta a tus amigos con acertijos ingeniosos. ¡Sumérgete en la diversión y comparte la emoción de dominar las palabras!",
+    no_script: "Necesitas activar JavaScript para usar esta aplicación.",
+    make_your_own: "Crea tu propio juego de palabras",
+    new_rules: "Sube una lista de palabras y crea un juego nuevo. Comparte el enlace con tus amigos.",
+    new_rules_daily: "Cada día se elige una palabra al azar de la lista. Tienes 6 intentos para resolver el reto. El color de las casillas cambia según lo acertado de cada intento.",
+    new_intro: "Crea tu propio juego de palabras. Reta a tus amigos.",
+    name_label: "Ponle nombre a tu juego",
+    name_placeholder: "Algo pegadizo, 3-12 caracteres",
+    description_label: "Descríbelo",
+    description_placeholder: "Presenta el juego en 140 caracteres como máximo",
+    words_label: "Lista de palabras",
+    words_placeholder: "7-365 palabras, de 3 a 10 letras cada una",
+    too_few_words: "Necesitamos al menos 7 palabras",
+    ai_hint: r#"<a href="https://chat.openai.com" target="_blank">Pide a una IA</a> que genere una lista de palabras, p. ej., <span>«Dame una lista de 100 especias de una sola palabra, cada una con menos de 10 letras»</span>"#,
+    match_words: "Según la lista de palabras",
+    cyrillic: "ЙЦУКЕН (cirílico)",
+    greek: "Griego",
+    hebrew: "Hebreo",
+    arabic: "Árabe",
+    direction: "Dirección de escritura",
+    ltr: "De izquierda a derecha",
+    rtl: "De derecha a izquierda",
+    language: "Idioma",
+    player_language: "El idioma del jugador",
+    origins_label: "Insertar en otros sitios (opcional)",
+    origins_placeholder: "p. ej., https://intranet.example.com",
+    make_it: "Crear ✨",
+    ready: "Listo para jugar",
+    created: "¡Enhorabuena, has creado tu propio juego de palabras! Comparte este enlace con tus amigos (o haz clic para jugar ya):",
+    share_link: "Compartir 🔗",
+    embed_snippet: "Insértalo en tu sitio con este código:",
+    copy_snippet: "Copiar código 📋",
+    make_another: "Crear otro",
+    built_by: r#"Hecho por <a href="https://doramilitaru.com" target="_blank">Dora</a> 👩🏻‍🔧 con <a href="https://www.fastly.com/products/edge-compute" target="_blank">Fastly Compute@Edge</a> 🚀 y <a href="https://rust-lang.org/" target="_blank">Rust</a> 🦀"#,
+    client: ClientMessages {
+        correct: "lugar correcto",
+        near: "en la palabra, lugar equivocado",
+        wrong: "no está en la palabra",
+        you_win: "¡Has ganado! ❤️",
+        better_luck: "Más suerte la próxima vez 😓",
+        thanks: "¡Gracias!",
+        try_later: "Algo salió mal 🥲\nInténtalo más tarde",
+        copied: "Copiado al portapapeles\n📋",
+        copy: "Copia:",
+        check_out: "Echa un vistazo a este divertido juego de palabras, {0}: {1}",
+        not_on_list: "No está en la lista",
+        slow_down: "Más despacio ⏳\nInténtalo de nuevo en un momento",
+        went_wrong: "Algo salió mal",
+        enough_games: "Ya basta de juegos por hoy 🥲\nInténtalo mañana",
+        made_game: "¡He creado un juego de palabras! Echa un vistazo a «{0}» en: {1}",
+        too_short: "Demasiado corto",
+        game_exists: "Ese juego ya existe",
+    },
+};
@@ -0,0 +1,101 @@
+use super::{ClientMessages, Messages};
+
+pub static MESSAGES: Messages = Messages {
+    lang: "fr",
+    made_with: "Créé avec Yourdle - Créez votre propre jeu de mots",
+    how_to_play: "Comment jouer",
+    statistics: "Statistiques",
+    close: "Fermer",
+    rules: "Vous avez 6 essais. Après chaque essai, la couleur des cases indique si vous êtes proche du mot.",
+    example_correct: "La lettre L est à la bonne place.",
+    example_near: "La lettre M est mal placée.",
+    example_wrong: "La lettre O n'est pas dans le mot.",
+    number: "N°",
+    contrast: "Activer ou désactiver le contraste élevé",
+    create_your_own: "Créez le vôtre",
+    feedback: "Avis",
+    feedback_intro: "Prenez un moment pour partager vos impressions avec moi, et faisons ensemble de Yourdle une aventure de mots encore meilleure !",
+    feedback_greeting: "Salut Dora,",
+    feedback_placeholder: "Voici ce que j'en pense... (10 à 140 caractères)",
+    send: "Envoyer",
+    games: "Parties",
+    won: "Gagnées",
+    streak: "Série",
+    best_streak: "Meilleure série",
+    share: "Partager 💪",
+    distribution: "Répartition des essais",
+    board: "Essais",
+    guess_number: "Essai {0}",
+    your_guess: "Votre essai",
+    guess: "Valider",
+    keyboard: "Clavier",
+    enter: "Entrée",
+    backspace: "Effacer",
+    play: "Jouer à {0}",
+    embed_footer: r#"ou <a href="/new" target="_blank">créez votre propre</a> jeu de mots sur Yourdle 🚀"#,
+    footer: r##"Donnez votre <a id="showFeedback" href="#">avis</a>, <a id="shareThis" href="#">partagez</a> ou <a href="/new" class="diy" target="_self">créez votre propre</a> jeu de mots 🚀"##,
+    summary: "Essai {0} sur {1}, {2} : {3}.",
+    summary_win: "Gagné !",
+    summary_lose: "Plus d'essais.",
+    game_over: "La partie du jour est terminée",
+    wrong_length: "Les mots doivent avoir {0} lettres",
+    solved: "Quelqu'un a résolu {0} n° {1} en {2} !",
+    not_solved: "Quelqu'un n'a pas résolu {0} n° {1} cette fois-ci.",
+    hard_mode: "En mode difficile : chaque essai a utilisé tous les indices révélés avant lui.",
+    verified: "Ce résultat est signé par Yourdle, il est donc authentique. Les lettres restent cachées jusqu'à ce que vous jouiez.",
+    new_game: "Nouveau jeu",
+    tagline: "Yourdle - Créez votre propre jeu de mots",
+    new_description: "Créez votre propre jeu de mots. Mettez vos amis au défi avec des énigmes astucieuses. Plongez dans des casse-têtes amusants et partagez le plaisir des mots !",
+    no_script: "Vous devez activer JavaScript pour utiliser cette application.",
+    make_your_own: "Créez votre propre jeu de mots",
+    new_rules: "Importez une liste de mots et créez un nouveau jeu. Partagez le lien avec vos amis.",
+    new_rules_daily: "Chaque jour, un mot est tiré au hasard dans la liste. Vous avez 6 essais pour le trouver. La couleur des cases change selon la justesse de chaque essai.",
+    new_intro: "Créez votre propre jeu de mots. Mettez vos amis au défi.",
+    name_label: "Nommez votre jeu",
+    name_placeholder: "Accrocheur, 3 à 12 caractères",
+    description_label: "Décrivez-le",
+    description_placeholder: "Présentez le jeu en 140 caractères maximum",
+    words_label: "Liste de mots",
+    words_placeholder: "7 à 365 mots, de 3 à 10 lettres chacun",
+    too_few_words: "Il faut au moins 7 mots",
+    ai_hint: r#"<a href="https://chat.openai.com" target="_blank">Demandez à une IA</a> de générer une liste de mots, par ex. <span>« Donne-moi une liste de 100 épices en un seul mot, de moins de 10 lettres chacune »</span>"#,
+    match_words: "Selon la liste de mots",
+    cyrillic: "ЙЦУКЕН (cyrillique)",
+    greek: "Grec",
+    hebrew: "Hébreu",
+    arabic: "Arabe",
+    direction: "Sens d'écriture",
+    ltr: "De gauche à droite",
+    rtl: "De droite à gauche",
+    language: "Langue",
+    player_language: "La langue du joueur",
+    origins_label: "Intégrer sur d'autres sites (facultatif)",
+    origins_placeholder: "par ex. https://intranet.example.com",
+    make_it: "Créer ✨",
+    ready: "Prêt à jouer",
+    created: "Félicitations, vous avez créé votre propre jeu de mots ! Partagez ce lien avec vos amis (ou cliquez pour jouer tout de suite) :",
+    share_link: "Partager 🔗",
+    embed_snippet: "Intégrez-le à votre site avec ce code :",
+    copy_snippet: "Copier le code 📋",
+    make_another: "En créer un autre",
+    built_by: r#"Créé par <a href="https://doramilitaru.com" target="_blank">Dora</a> 👩🏻‍🔧 avec <a href="https://www.fastly.com/products/edge-compute" target="_blank">Fastly Compute@Edge</a> 🚀 &amp; <a href="https://rust-lang.org/" target="_blank">Rust</a> 🦀"#,
+    client: ClientMessages {
+        correct: "bonne place",
+        near: "dans le mot, mal placée",
+        wrong: "pas dans le mot",
+        you_win: "Gagné ❤️",
+        better_luck: "Ce sera pour la prochaine fois 😓",
+        thanks: "Merci !",
+        try_later: "Un problème est survenu 🥲\nRéessayez plus tard",
+        copied: "Copié dans le presse-papiers\n📋",
+        copy: "Copiez :",
+        check_out: "Découvrez ce jeu de mots amusant, {0} : {1}",
+        not_on_list: "Ce mot n'est pas dans la liste",
+        slow_down: "Doucement ⏳\nRéessayez dans un instant",
+        went_wrong: "Un problème est survenu",
+        enough_games: "Assez de jeux pour aujourd'hui 🥲\nRéessayez demain",
+        made_game: "J'ai créé un jeu de mots ! Découvrez « {0} » ici : {1}",
+        too_short: "Trop court",
+        game_exists: "Ce jeu existe déjà",
+    },
+};
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+
+// Keys per row when laying out the letters of an alphabet without a known layout.
+const ROW_LENGTH: usize = 10;
+
+// On-screen keyboard layouts, chosen per game.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    // Derived from the alphabet used in the word list.
+    #[default]
+    Auto,
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Cyrillic,
+    Greek,
+    Hebrew,
+    Arabic,
+}
+
+// A row of keys, and whether it gets spacers or the Enter and Backspace keys either side.
+pub struct KeyRow {
+    pub letters: Vec<char>,
+    pub spacers: bool,
+    pub actions: bool,
+}
+
+impl Layout {
+    fn rows(&self) -> &'static [&'static str] {
+        match self {
+            Layout::Auto => &[],
+            Layout::Qwerty => &["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+            Layout::Azerty => &["azertyuiop", "qsdfghjklm", "wxcvbn"],
+            Layout::Qwertz => &["qwertzuiopü", "asdfghjklöä", "yxcvbnm"],
+            Layout::Cyrillic => &["йцукенгшщзхъ", "фывапролджэ", "ячсмитьбюё"],
+            Layout::Greek => &["ερτυθιοπ", "ασδφγηξκλ", "ζχψωβνμ"],
+            Layout::Hebrew => &["קראטוןםפ", "שדגכעיחלךף", "זסבהנמצתץ"],
+            Layout::Arabic => &["ضصثقفغعهخحجد", "شسيبلاتنمكط", "ذئءؤرىةوزظ"],
+        }
+    }
+
+    // Pick a layout for the alphabet used in a word list.
+    fn detect(letters: &BTreeSet<char>) -> Layout {
+        if letters.iter().any(|c| ('а'..='я').contains(c) || *c == 'ё') {
+            Layout::Cyrillic
+        } else if letters.iter().any(|c| ('α'..='ω').contains(c)) {
+            Layout::Greek
+        } else if letters.iter().any(|c| ('א'..='ת').contains(c)) {
+            Layout::Hebrew
+        } else if letters.iter().any(|c| ('ء'..='ي').contains(c)) {
+            Layout::Arabic
+        } else if letters.iter().any(char::is_ascii_lowercase) {
+            Layout::Qwerty
+        } else {
+            Layout::Auto
+        }
+    }
+
+    // The keyboard for a word list: the layout's rows, plus a row on top for any letters used in
+    // the words that the layout doesn't have (or rows of the whole alphabet, if there's no layout).
+    pub fn keyboard(&self, words: &[String]) -> Vec<KeyRow> {
+        let letters: BTreeSet<char> = words
+            .iter()
+            .flat_map(|word| word.chars())
+            .flat_map(char::to_lowercase)
+            .collect();
+        let layout = match self {
+            Layout::Auto => Layout::detect(&letters),
+            layout => *layout,
+        };
+        let rows = layout.rows();
+        let extra: Vec<char> = letters
+            .into_iter()
+            .filter(|letter| !rows.iter().any(|row| row.contains(*letter)))
+            .collect();
+        let mut keyboard: Vec<KeyRow> = extra
+            .chunks(ROW_LENGTH)
+            .map(|letters| KeyRow {
+                letters: letters.to_vec(),
+                spacers: false,
+                actions: false,
+            })
+            .collect();
+        keyboard.extend(rows.iter().enumerate().map(|(i, row)| KeyRow {
+            letters: row.chars().collect(),
+            spacers: i > 0,
+            actions: false,
+        }));
+        if let Some(last) = keyboard.last_mut() {
+            last.spacers = false;
+            last.actions = true;
+        }
+        keyboard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    fn letters(keyboard: &[KeyRow]) -> Vec<String> {
+        keyboard
+            .iter()
+            .map(|row| row.letters.iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn detects_cyrillic_words() {
+        let words = words(&["ПРИВЕТ", "СЛОВО", "ЁЖИК"]);
+        let letters: BTreeSet<char> = "приветсловоёжик".chars().collect();
+        assert_eq!(Layout::detect(&letters), Layout::Cyrillic);
+        assert_eq!(
+            Layout::Auto.keyboard(&words).len(),
+            Layout::Cyrillic.rows().len()
+        );
+    }
+
+    #[test]
+    fn adds_a_row_for_letters_missing_from_the_layout() {
+        let words = words(&["ÜBUNG", "HALLO"]);
+        let keyboard = Layout::Auto.keyboard(&words);
+        assert_eq!(
+            letters(&keyboard),
+            ["ü", "qwertyuiop", "asdfghjkl", "zxcvbnm"]
+        );
+        assert!(!keyboard[0].spacers && !keyboard[0].actions);
+        assert!(keyboard[3].actions);
+        // QWERTZ already has it.
+        assert_eq!(Layout::Qwertz.keyboard(&words).len(), 3);
+    }
+
+    #[test]
+    fn lays_out_alphabets_without_a_layout_in_order() {
+        let words = words(&["სიტყვა", "თამაში"]);
+        let letters_used: BTreeSet<char> = "სიტყვათამაში".chars().collect();
+        assert_eq!(Layout::detect(&letters_used), Layout::Auto);
+        let keyboard = Layout::Auto.keyboard(&words);
+        assert_eq!(letters(&keyboard), ["ავთიმსტყშ"]);
+        assert!(keyboard[0].actions);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{config, state, utils};
+use fastly::erl::{Penaltybox, RateCounter, RateWindow, ERL};
+use fastly::http::{header, StatusCode};
+use fastly::{KVStore, Request, Response};
+use std::time::Duration;
+
+const RATE_COUNTER_NAME: &str = "yourdle-rc";
+const PENALTY_BOX_NAME: &str = "yourdle-pb";
+const PENALTY_SECS: u64 = 120; // 2 minutes
+const QUOTA_STORE_NAME: &str = "yourdle-stats";
+const NEW_GAME_QUOTA: u32 = 5; // per day
+const QUOTA_TTL: u64 = 60 * 60 * 24; // 1 day
+
+// Actions that are rate limited.
+#[derive(Clone, Copy, Debug)]
+pub enum Action {
+    Guess,
+    New,
+    Validate,
+    Feedback,
+    Identity,
+}
+
+impl Action {
+    fn name(&self) -> &'static str {
+        match self {
+            Action::Guess => "guess",
+            Action::New => "new",
+            Action::Validate => "validate",
+            Action::Feedback => "feedback",
+            Action::Identity => "identity",
+        }
+    }
+
+    // Maximum average requests per second, configurable as `rate-limit-{action}`.
+    fn limit(&self) -> u32 {
+        let default = match self {
+            Action::Guess => 2,
+            Action::New | Action::Validate | Action::Feedback | Action::Identity => 1,
+        };
+        config::get(&format!("rate-limit-{}", self.name()))
+            .and_then(|limit| limit.parse().ok())
+            .unwrap_or(default)
+    }
+}
+
+// Check the rate of an action per client IP and per user, and respond with 429 if exceeded.
+pub fn check(req: &Request, action: Action) -> Option<Response> {
+    let penalty = config::get("rate-limit-penalty")
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or(PENALTY_SECS);
+    let limiter = ERL::open(
+        RateCounter::open(RATE_COUNTER_NAME),
+        Penaltybox::open(PENALTY_BOX_NAME),
+    );
+    for entry in identities(req) {
+        // Fail open if the rate counter is unavailable.
+        if let Ok(true) = limiter.check_rate(
+            &format!("{}:{}", action.name(), entry),
+            1,
+            RateWindow::SixtySecs,
+            action.limit(),
+            Duration::from_secs(penalty),
+        ) {
+            return Some(
+                Response::from_status(StatusCode::TOO_MANY_REQUESTS)
+                    .with_header(header::RETRY_AFTER, penalty.to_string()),
+            );
+        }
+    }
+    None
+}
+
+// Check the daily quota of new games per client IP and per user, and respond with 429 if exceeded.
+pub fn check_quota(req: &Request) -> Option<Response> {
+    let quota = config::get("new-game-quota")
+        .and_then(|quota| quota.parse().ok())
+        .unwrap_or(NEW_GAME_QUOTA);
+    if identities(req)
+        .iter()
+        .any(|identity| quota_used(identity) >= quota)
+    {
+        return Some(
+            Response::from_status(StatusCode::TOO_MANY_REQUESTS).with_header(
+                header::RETRY_AFTER,
+                utils::secs_until_tomorrow().to_string(),
+            ),
+        );
+    }
+    None
+}
+
+// Count a new game against the daily quota of the client IP and user.
+pub fn count_quota(req: &Request) {
+    if let Ok(Some(quota_store)) = KVStore::open(QUOTA_STORE_NAME) {
+        for identity in identities(req) {
+            let used = quota_used(&identity);
+            // Quotas are only needed for the day they're counted on.
+            quota_store
+                .build_insert()
+                .time_to_live(Duration::from_secs(QUOTA_TTL))
+                .execute(&quota_key(&identity), (used + 1).to_string())
+                .ok();
+        }
+    }
+}
+
+// The client IP and, if known, the user ID making a request.
+fn identities(req: &Request) -> Vec<String> {
+    let cookie = req.get_header_str("cookie").unwrap_or_default();
+    vec![
+        req.get_client_ip_addr().map(|ip| ip.to_string()),
+        state::find_bearer_id(req).or_else(|| state::find_user_id(cookie)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+// Quotas are kept under the reserved `new` prefix, so they can't clash with game stats.
+fn quota_key(identity: &str) -> String {
+    format!("new-{}-{}", utils::date_iso8601(), identity)
+}
+
+fn quota_used(identity: &str) -> u32 {
+    match KVStore::open(QUOTA_STORE_NAME) {
+        Ok(Some(quota_store)) => match quota_store.lookup(&quota_key(identity)) {
+            Ok(mut used) => used.take_body().into_string().parse().unwrap_or(0),
+            _ => 0,
+        },
+        _ => 0,
+    }
+}
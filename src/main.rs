@@ -1,154 +1,406 @@
-use fastly::http::{header, Method, StatusCode};
+use askama::Template;
+use fastly::http::{header, Method, StatusCode, Url};
 use fastly::{mime, Error, KVStore, Request, Response};
+mod alphabet;
+mod api;
+mod assets;
+mod challenge;
+mod config;
+mod cors;
+mod csrf;
 mod game;
 mod guess;
+mod i18n;
+mod keyboard;
+mod limits;
+mod pages;
+mod router;
+mod security;
 mod state;
+mod token;
 mod utils;
 
 use game::{GameData, GameDataForm};
-use guess::{Guess, Guesses};
-
-// const LONG_CACHE: &str = "public, max-age=21600, immutable";
-const LONG_CACHE: &str = "public, max-age=3600, must-revalidate";
-// const LONG_CACHE: &str = "private, no-cache, max-age=0, no-store";
+use guess::{Guess, Guesses, Match, Share, SharedResult};
+use i18n::Locale;
+use limits::Action;
+use pages::{GameCard, GamePage, GridCard, IndexPage, NewPage, NotFoundPage, ResultPage};
+use router::{Params, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
 
 #[fastly::main]
-fn main(mut req: Request) -> Result<Response, Error> {
-    match req.get_method() {
-        &Method::GET | &Method::HEAD | &Method::POST => (),
-        _ => {
-            return Ok(Response::from_status(StatusCode::METHOD_NOT_ALLOWED)
-                .with_header(header::ALLOW, "GET, HEAD, POST"))
+fn main(req: Request) -> Result<Response, Error> {
+    // Allowed cross-origin API requests get CORS headers on whatever response they receive.
+    let cors = cors::check(&req);
+    // Static assets, under their original and content-hashed paths.
+    let resp = assets::ASSETS
+        .iter()
+        .fold(Router::new(not_found), |router, asset| {
+            router
+                .get(asset.path, assets::serve)
+                .get(asset.hashed_path, assets::serve)
+        })
+        .get("/", |_, _| {
+            // The home page is only in English for now.
+            let page = IndexPage {
+                t: Locale::En.messages(),
+            };
+            Ok(Response::from_status(StatusCode::OK).with_body_text_html(&page.render()?))
+        })
+        // All other routes.
+        .post("/feedback", feedback)
+        .post("/validate", validate)
+        .get("/new", new_game_page)
+        .post("/new", new_game)
+        // API routes (yourdle.edgecomptech.com/api/v1/...).
+        .get("/api/v1/openapi.json", api::get_openapi)
+        .get("/api/v1/challenge", api::get_challenge)
+        .post("/api/v1/identity", api::create_identity)
+        .post("/api/v1/games", api::create_game)
+        .get("/api/v1/games/{slug}", api::get_game)
+        .get("/api/v1/games/{slug}/today", api::get_puzzle)
+        .get("/api/v1/games/{slug}/stats", api::get_stats)
+        .post("/api/v1/games/{slug}/guesses", api::submit_guess)
+        .get("/oembed", oembed)
+        // Game routes (yourdle.edgecomptech.com/game-slug).
+        .get("/{slug}/card.svg", card)
+        .get("/{slug}/result/{token}", result)
+        .get("/{slug}/result/{token}/grid.svg", grid)
+        .get("/{slug}/embed", |req, params| game(req, params, true))
+        .post("/{slug}/embed", |req, params| game(req, params, true))
+        .get("/{slug}", |req, params| game(req, params, false))
+        .post("/{slug}", |req, params| game(req, params, false))
+        .handle(req)?;
+    // Every response gets the security headers.
+    let resp = security::finalize(resp);
+    Ok(match cors {
+        Some(cors) => cors.apply(resp),
+        None => resp,
+    })
+}
+
+// Save feedback from a game page.
+fn feedback(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    if let Some(resp) = csrf::check(req).or_else(|| limits::check(req, Action::Feedback)) {
+        return Ok(resp);
+    }
+    if let Ok(Some(feedback_store)) = KVStore::open("yourdle-feedback") {
+        let feedback = req.take_body_str();
+        let cookie = req.get_header_str("cookie").unwrap_or_default();
+        let user_id = state::get_user_id(cookie);
+        if let Ok(_) = feedback_store.insert(
+            &format!("{}-{}", utils::timestamp_now(), user_id),
+            utils::truncate_to_chars(&feedback, 300),
+        ) {
+            return Ok(with_cookie(StatusCode::OK, &state::set_user_id(&user_id)));
         }
+    }
+    Ok(Response::from_status(StatusCode::BAD_REQUEST))
+}
+
+// Check a new game's name is available.
+fn validate(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    if let Some(resp) = csrf::check(req).or_else(|| limits::check(req, Action::Validate)) {
+        return Ok(resp);
+    }
+    if let Ok(form) = req.take_body_json::<GameDataForm>() {
+        if GameData::check_not_exists(&form.game).is_ok() {
+            return Ok(Response::from_status(StatusCode::OK));
+        }
+    }
+    Ok(Response::from_status(StatusCode::BAD_REQUEST))
+}
+
+// Render the new game form.
+fn new_game_page(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    let cookie = req.get_header_str("cookie").unwrap_or_default();
+    let user_id = state::get_user_id(cookie);
+    let page = NewPage {
+        challenge: challenge::issue().unwrap_or_default(),
+        csrf: csrf::generate(&user_id),
+        t: Locale::negotiate(req, None).messages(),
     };
-    // Route requests.
-    match req.get_path() {
-        // Static assets.
-        "/favicon.png" => Ok(png(include_bytes!("browser/images/favicon.png"))),
-        "/card.png" => Ok(png(include_bytes!("browser/images/card.png"))),
-        "/yourdle.svg" => Ok(svg(include_str!("browser/images/yourdle.svg"))),
-        "/info.svg" => Ok(svg(include_str!("browser/images/info.svg"))),
-        "/contrast.svg" => Ok(svg(include_str!("browser/images/contrast.svg"))),
-        "/stats.svg" => Ok(svg(include_str!("browser/images/stats.svg"))),
-        "/style.css" => Ok(text(mime::TEXT_CSS, include_str!("browser/style.css"))),
-        "/script.js" => Ok(js(include_bytes!("browser/script.js"))),
-        "/new.js" => Ok(js(include_bytes!("browser/new.js"))),
-        "/" => Ok(html(include_str!("browser/index.html"))),
-        // All other routes.
-        "/feedback" => {
-            if req.get_method() == &Method::POST {
-                if let Ok(Some(mut feedback_store)) = KVStore::open("yourdle-feedback") {
-                    let feedback = req.take_body_str();
-                    let cookie = req.get_header_str("cookie").unwrap_or_default();
-                    let user_id = state::get_user_id(cookie);
-                    if let Ok(_) = feedback_store.insert(
-                        &format!("{}-{}", utils::timestamp_now(), user_id),
-                        utils::truncate_to_chars(&feedback, 300),
-                    ) {
-                        return Ok(with_cookie(StatusCode::OK, &state::set_user_id(&user_id)));
-                    }
-                }
-            }
-            Ok(Response::from_status(StatusCode::BAD_REQUEST))
+    Ok(with_cookie(StatusCode::OK, &state::set_user_id(&user_id))
+        .with_header(header::VARY, "Accept-Language")
+        .with_body_text_html(&page.render()?))
+}
+
+// Create a new game and respond with its slug.
+fn new_game(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    if let Some(resp) = GameData::check_new(req) {
+        return Ok(resp);
+    }
+    match GameData::create(req) {
+        Ok(game_data) => {
+            Ok(Response::from_status(StatusCode::OK).with_body_text_plain(&game_data.slug))
         }
-        "/validate" => {
-            if let Ok(form) = req.take_body_json::<GameDataForm>() {
-                if GameData::check_not_exists(&form.game).is_ok() {
-                    return Ok(Response::from_status(StatusCode::OK));
-                }
-            }
-            Ok(Response::from_status(StatusCode::BAD_REQUEST))
+        Err((status, _)) => Ok(Response::from_status(status)),
+    }
+}
+
+// Render a game (or its embeddable variant), or record a guess if the guess query parameter is set
+// or a guess is posted.
+#[utoipa::path(
+    get,
+    path = "/{slug}",
+    params(
+        ("slug" = String, Path, description = "The game's slug"),
+        ("guess" = String, Query, description = "A guess at today's word")
+    ),
+    responses(
+        (status = 200, description = "The player's stats after the guess", body = GuessResponse),
+        (status = 403, description = "Invalid CSRF token"),
+        (status = 404, description = "The word isn't on the list"),
+        (status = 422, description = "The word has the wrong length"),
+        (status = 429, description = "Rate limited")
+    )
+)]
+fn game(req: &mut Request, params: &Params, embed: bool) -> Result<Response, Error> {
+    let game = &params["slug"];
+    // Load game data.
+    let mut game_data = match GameData::load(game) {
+        Ok(game_data) => game_data,
+        _ => return not_found(req, params),
+    };
+    // HEAD requests only need to know the game exists.
+    if req.get_method() == Method::HEAD {
+        return Ok(Response::from_status(StatusCode::OK).with_content_type(mime::TEXT_HTML_UTF_8));
+    }
+    // Load today's word.
+    let (word, index, _) = game_data.get_word().unwrap();
+    // Get the user ID from the cookie (or create a new one).
+    let cookie = req.get_header_str("cookie").unwrap_or_default();
+    let user_id = state::get_user_id(cookie);
+    let user_cookie = match embed {
+        true => state::set_embedded_user_id(&user_id),
+        false => state::set_user_id(&user_id),
+    };
+    // Load game stats.
+    let mut guesses = state::load_guesses(req, game, &user_id, word.chars().count());
+    let t = Locale::negotiate(req, game_data.locale).messages();
+    // Record a guess, if the guess query parmeter is set.
+    if let Some(guess) = req.get_query_parameter("guess") {
+        if let Some(resp) = csrf::check(req).or_else(|| limits::check(req, Action::Guess)) {
+            return Ok(resp);
         }
-        "/new" => match req.get_method() {
-            &Method::POST => {
-                if let Ok(form) = req.take_body_json::<GameDataForm>() {
-                    if let Ok(mut game_data) = GameData::from_form(form) {
-                        if game_data.save().is_ok() {
-                            return Ok(Response::from_status(StatusCode::OK)
-                                .with_body_text_plain(&game_data.slug));
-                        }
-                    }
-                }
-                Ok(Response::from_status(StatusCode::BAD_REQUEST))
-            }
-            _ => Ok(html(include_str!("browser/new.html"))),
-        },
-        // Game routes (yourdle.edgecomptech.com/game-slug).
-        req_path => {
-            let game = &req_path[1..];
-            if !game.contains("/") {
-                // Load game data.
-                if let Ok(mut game_data) = game::GameData::load(game) {
-                    // Load today's word.
-                    let (word, _, _) = game_data.get_word().unwrap();
-                    // Get the user ID from the cookie (or create a new one).
-                    let cookie = req.get_header_str("cookie").unwrap_or_default();
-                    let user_id = state::get_user_id(cookie);
-                    // Load game stats.
-                    let mut guesses = Guesses::load(&game, &user_id, word.len());
-                    // Record a guess, if the guess query parmeter is set.
-                    if let Some(guess) = req.get_query_parameter("guess") {
-                        // Check if the guessed word is in the game's list of words.
-                        if game_data.validate_word(guess) {
-                            // Update guesses (save stats) and respond with stats.
-                            guesses.update(&game, &user_id, Guess::new(&guess, &word))?;
-                            return Ok(with_cookie(StatusCode::OK, &state::set_user_id(&user_id))
-                                .with_body_json(&guesses)?);
-                        }
-                        // Respond with 404 if the word isn't in the list.
-                        return Ok(with_cookie(
-                            StatusCode::NOT_FOUND,
-                            &state::set_user_id(&user_id),
-                        ));
-                    }
-                    // Render the game index.
-                    return Ok(with_cookie(StatusCode::OK, &state::set_user_id(&user_id))
-                        .with_body_text_html(&format!(
-                            "{}{}{}",
-                            game_data,
-                            guesses,
-                            include_str!("browser/end.html")
-                        )));
-                }
-            }
-            // Respond with 404 for anything else.
-            Ok(Response::from_status(StatusCode::NOT_FOUND)
-                .with_body_text_html(&include_str!("browser/404.html")))
+        // Guesses need as many letters as today's word.
+        if guess.chars().count() != word.chars().count() {
+            return Ok(with_cookie(StatusCode::UNPROCESSABLE_ENTITY, &user_cookie));
+        }
+        // Check if the guessed word is in the game's list of words.
+        if game_data.validate_word(guess) {
+            // Update guesses (save stats) and respond with stats.
+            guesses.update(game, &user_id, Guess::new(guess, &word))?;
+            let mut resp = with_cookie(StatusCode::OK, &user_cookie);
+            state::save_progress(&mut resp, &guesses, game, &user_id).map_err(Error::msg)?;
+            return Ok(resp.with_body_json(&GuessResponse {
+                keyboard: guesses.letter_states(),
+                summary: guesses.summary(t),
+                share: guesses.share(&game_data, index + 1, &utils::game_url(req, game)),
+                guesses: &guesses,
+            })?);
+        }
+        // Respond with 404 if the word isn't in the list.
+        return Ok(with_cookie(StatusCode::NOT_FOUND, &user_cookie));
+    }
+    // Record a guess posted with the no-JavaScript form, then show the updated page.
+    let mut message = None;
+    if req.get_method() == Method::POST {
+        let form = req.take_body_form::<GuessForm>().unwrap_or_default();
+        if let Some(resp) =
+            csrf::check_form(req, &form.csrf).or_else(|| limits::check(req, Action::Guess))
+        {
+            return Ok(resp);
+        }
+        let guess = form.guess.trim().to_uppercase();
+        if guesses.is_over() {
+            message = Some(t.game_over.to_owned());
+        } else if guess.chars().count() != word.chars().count() {
+            message = Some(i18n::fill(t.wrong_length, [word.chars().count()]));
+        } else if !game_data.validate_word(&guess) {
+            message = Some(t.client.not_on_list.to_owned());
+        } else {
+            guesses.update(game, &user_id, Guess::new(&guess, &word))?;
+            // Redirect, so reloading the page doesn't post the guess again.
+            let mut resp = with_cookie(StatusCode::SEE_OTHER, &user_cookie)
+                .with_header(header::LOCATION, req.get_path());
+            state::save_progress(&mut resp, &guesses, game, &user_id).map_err(Error::msg)?;
+            return Ok(resp);
         }
     }
+    // Render the game index.
+    let url = utils::game_url(req, game);
+    let page = GamePage {
+        game: &game_data,
+        guesses: &guesses,
+        csrf: csrf::generate(&user_id),
+        embed,
+        message,
+        share: guesses.share(&game_data, index + 1, &url),
+        number: index + 1,
+        url,
+        t,
+    };
+    let mut resp = with_cookie(StatusCode::OK, &user_cookie).with_body_text_html(&page.render()?);
+    // Games without a language of their own are shown in the player's.
+    if game_data.locale.is_none() {
+        resp.set_header(header::VARY, "Accept-Language");
+    }
+    if embed {
+        // Only the game's own origins may frame it.
+        resp.set_header(
+            header::CONTENT_SECURITY_POLICY,
+            format!("frame-ancestors 'self' {}", game_data.origins.join(" ")).trim_end(),
+        );
+    }
+    Ok(resp)
 }
 
-// Response helpers (useful for serving the frontend).
-fn long_cache_resp(mime_type: mime::Mime) -> Response {
-    Response::from_status(StatusCode::OK)
-        .with_content_type(mime_type)
-        .with_header(header::CACHE_CONTROL, LONG_CACHE)
+// Render a game's cover card as SVG, for link previews of the game page.
+fn card(req: &mut Request, params: &Params) -> Result<Response, Error> {
+    let game_data = match GameData::load(&params["slug"]) {
+        Ok(game_data) => game_data,
+        _ => return not_found(req, params),
+    };
+    Ok(Response::from_status(StatusCode::OK)
+        .with_content_type(mime::IMAGE_SVG)
+        .with_header(header::CACHE_CONTROL, assets::LONG_CACHE)
+        .with_body(GameCard { game: &game_data }.render()?))
 }
 
-fn bytes(mime_type: mime::Mime, body: &[u8]) -> Response {
-    long_cache_resp(mime_type).with_body_octet_stream(body)
+// The result from a signed share link, with its game and grid. Links that don't verify, or don't
+// belong to the game, can't be found.
+fn shared_result(params: &Params) -> Option<(SharedResult, GameData, Vec<Vec<Match>>)> {
+    let shared = SharedResult::verify(&params["slug"], &params["token"]).ok()?;
+    let game_data = GameData::load(&shared.slug).ok()?;
+    let rows = guess::parse_pattern(&shared.pattern)
+        .filter(|_| (1..=game_data.number()).contains(&shared.number))?;
+    Some((shared, game_data, rows))
 }
 
-fn text(mime_type: mime::Mime, body: &str) -> Response {
-    long_cache_resp(mime_type).with_body(body)
+// Render the grid of a result from a signed share link as an SVG card, e.g. as the preview
+// image of its page, so the card can't be made up either.
+fn grid(req: &mut Request, params: &Params) -> Result<Response, Error> {
+    let (shared, game_data, rows) = match shared_result(params) {
+        Some(shared_result) => shared_result,
+        None => return not_found(req, params),
+    };
+    let card = GridCard {
+        game: &game_data,
+        number: shared.number,
+        rows,
+        contrast: req.get_query_parameter("contrast").is_some(),
+    };
+    Ok(Response::from_status(StatusCode::OK)
+        .with_content_type(mime::IMAGE_SVG)
+        .with_header(header::CACHE_CONTROL, assets::LONG_CACHE)
+        .with_body(card.render()?))
 }
 
-fn png(body: &[u8]) -> Response {
-    bytes(mime::IMAGE_PNG, body)
+// Show a result from a signed share link, without the letters.
+fn result(req: &mut Request, params: &Params) -> Result<Response, Error> {
+    let (shared, game_data, rows) = match shared_result(params) {
+        Some(shared_result) => shared_result,
+        None => return not_found(req, params),
+    };
+    let url = utils::game_url(req, &shared.slug);
+    let page = ResultPage {
+        game: &game_data,
+        number: shared.number,
+        rows,
+        hard: shared.hard,
+        image: format!("{}/result/{}/grid.svg", url, params["token"]),
+        url,
+        t: Locale::negotiate(req, game_data.locale).messages(),
+    };
+    let mut resp = Response::from_status(StatusCode::OK).with_body_text_html(&page.render()?);
+    if game_data.locale.is_none() {
+        resp.set_header(header::VARY, "Accept-Language");
+    }
+    Ok(resp)
 }
 
-fn svg(body: &str) -> Response {
-    text(mime::IMAGE_SVG, body)
+// Describe a game URL for oEmbed consumers, which render it as an iframe of the embed variant.
+fn oembed(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    if req.get_query_parameter("format").unwrap_or("json") != "json" {
+        return Ok(Response::from_status(StatusCode::NOT_IMPLEMENTED));
+    }
+    let url = match req.get_query_parameter("url").map(Url::parse) {
+        Some(Ok(url)) => url,
+        _ => return Ok(Response::from_status(StatusCode::BAD_REQUEST)),
+    };
+    // Only this service's own game URLs can be embedded.
+    if url.host_str() != req.get_url().host_str() {
+        return Ok(Response::from_status(StatusCode::NOT_FOUND));
+    }
+    let origin = req.get_url().origin().ascii_serialization();
+    let segments: Vec<&str> = url.path_segments().map_or(vec![], |s| s.collect());
+    let slug = match segments[..] {
+        [slug] | [slug, "embed"] => slug.to_owned(),
+        _ => return Ok(Response::from_status(StatusCode::NOT_FOUND)),
+    };
+    let game_data = match GameData::load(&slug) {
+        Ok(game_data) => game_data,
+        _ => return Ok(Response::from_status(StatusCode::NOT_FOUND)),
+    };
+    let dimension = |param: &str, default: u32| -> u32 {
+        req.get_query_parameter(param)
+            .and_then(|value| value.parse().ok())
+            .map_or(default, |max: u32| max.min(default))
+    };
+    let (width, height) = (dimension("maxwidth", 400), dimension("maxheight", 640));
+    let embed_url = format!("{}/{}/embed", origin, slug);
+    let iframe = format!(
+        r#"<iframe src="{}" width="{}" height="{}" style="border:0" title="{}" loading="lazy"></iframe>"#,
+        embed_url,
+        width,
+        height,
+        askama::filters::escape(askama::Html, &game_data.game)?,
+    );
+    Ok(Response::from_status(StatusCode::OK)
+        .with_header(header::CACHE_CONTROL, assets::LONG_CACHE)
+        .with_body_json(&serde_json::json!({
+            "version": "1.0",
+            "type": "rich",
+            "provider_name": "Yourdle",
+            "provider_url": origin,
+            "title": game_data.game,
+            "html": iframe,
+            "width": width,
+            "height": height,
+        }))?)
+}
+
+// Respond with 404 for anything else.
+fn not_found(req: &mut Request, _: &Params) -> Result<Response, Error> {
+    if req.get_path().starts_with(api::PREFIX) {
+        return Ok(api::not_found("Not found"));
+    }
+    Ok(Response::from_status(StatusCode::NOT_FOUND).with_body_text_html(&NotFoundPage.render()?))
 }
 
-fn js(body: &[u8]) -> Response {
-    bytes(mime::APPLICATION_JAVASCRIPT_UTF_8, body)
+// A guess posted with the no-JavaScript form on the game page.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct GuessForm {
+    guess: String,
+    csrf: String,
 }
 
-fn html(body: &str) -> Response {
-    Response::from_status(StatusCode::OK).with_body_text_html(body)
+// The player's stats, keyboard letter states, a summary for screen readers and, once the game is
+// over, the result to share, in response to a guess from the game page.
+#[derive(Serialize, ToSchema)]
+struct GuessResponse<'a> {
+    #[serde(flatten)]
+    guesses: &'a Guesses,
+    keyboard: BTreeMap<String, Match>,
+    summary: String,
+    share: Option<Share>,
 }
 
+// Response helpers.
 fn with_cookie(status: StatusCode, cookie: &str) -> Response {
     Response::from_status(status).with_header(header::SET_COOKIE, cookie)
 }
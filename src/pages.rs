@@ -0,0 +1,315 @@
+use crate::alphabet::Direction;
+use crate::game::GameData;
+use crate::guess::{Guesses, Match, Share, TRIES};
+use crate::i18n::{self, Messages};
+use askama::Template;
+
+// The game page, put together from the start, stats and end templates.
+#[derive(Template)]
+#[template(
+    source = r#"{% include "start.html" %}{% include "stats.html" %}{% include "end.html" %}"#,
+    ext = "html"
+)]
+pub struct GamePage<'a> {
+    pub game: &'a GameData,
+    pub guesses: &'a Guesses,
+    pub csrf: String,
+    // Compact layout without the header, for embedding in other sites.
+    pub embed: bool,
+    // Feedback on a guess posted without JavaScript.
+    pub message: Option<String>,
+    // Today's result, once the game is over.
+    pub share: Option<Share>,
+    // Today's puzzle number and the game's URL, for link previews.
+    pub number: i64,
+    pub url: String,
+    pub t: &'static Messages,
+}
+
+// The home page.
+#[derive(Template)]
+#[template(path = "index.html")]
+pub struct IndexPage {
+    pub t: &'static Messages,
+}
+
+// The new game page.
+#[derive(Template)]
+#[template(path = "new.html")]
+pub struct NewPage {
+    pub challenge: String,
+    pub csrf: String,
+    pub t: &'static Messages,
+}
+
+// A read-only page showing a result from a signed share link, with the grid but not the letters.
+#[derive(Template)]
+#[template(path = "result.html")]
+pub struct ResultPage<'a> {
+    pub game: &'a GameData,
+    pub number: i64,
+    pub rows: Vec<Vec<Match>>,
+    pub hard: bool,
+    // The game's URL, and a picture of the grid for link previews.
+    pub url: String,
+    pub image: String,
+    pub t: &'static Messages,
+}
+
+// The page for games (or anything else) that can't be found.
+#[derive(Template)]
+#[template(path = "404.html")]
+pub struct NotFoundPage;
+
+// A picture of a finished game's grid, without the letters, e.g. for link previews of shared
+// results.
+#[derive(Template)]
+#[template(path = "grid.svg")]
+pub struct GridCard<'a> {
+    pub game: &'a GameData,
+    pub number: i64,
+    pub rows: Vec<Vec<Match>>,
+    // Use the high contrast colours.
+    pub contrast: bool,
+}
+
+// A game's cover picture, with its name and description, for link previews of the game page.
+#[derive(Template)]
+#[template(path = "card.svg")]
+pub struct GameCard<'a> {
+    pub game: &'a GameData,
+}
+
+// The size of the tiles on a grid card, and the space between them, in pixels.
+pub const CARD_TILE: usize = 52;
+const CARD_GAP: usize = 8;
+// Cards are the size of a link preview image, with the grid below the headings.
+const CARD_WIDTH: usize = 1200;
+const CARD_GRID_TOP: usize = 200;
+
+// The size of the letter tiles on a game card, and how far down they are.
+pub const COVER_TILE: usize = 80;
+const COVER_TOP: usize = 160;
+// Descriptions are wrapped to lines of about this many characters.
+const COVER_LINE: usize = 44;
+
+// A tile on a grid or game card.
+pub struct CardTile {
+    pub x: usize,
+    pub y: usize,
+    pub fill: &'static str,
+    pub letter: Option<char>,
+}
+
+// The colour of a tile in a given state.
+fn tile_fill(state: Match, contrast: bool) -> &'static str {
+    match (state, contrast) {
+        (Match::Correct, false) => "#23e0a8",
+        (Match::Correct, true) | (Match::Near, false) => "#fca119",
+        (Match::Near, true) => "#04b2e1",
+        (Match::Wrong, _) => "#182931",
+    }
+}
+
+// A row of tiles on the game board.
+pub struct Row {
+    pub tiles: Vec<Tile>,
+    pub active: bool,
+}
+
+// A row of the on-screen keyboard, with spacers or the Enter and Backspace keys either side.
+pub struct KeyboardRow {
+    pub keys: Vec<Tile>,
+    pub spacers: bool,
+    pub actions: bool,
+}
+
+pub struct Tile {
+    pub letter: String,
+    pub state: &'static str,
+    // e.g. "A, correct position", once the letter has been guessed.
+    pub label: String,
+}
+
+impl GridCard<'_> {
+    // e.g. "#42 3/6", or "#42 X/6" for a loss.
+    fn heading(&self) -> String {
+        let won = self
+            .rows
+            .last()
+            .is_some_and(|row| row.iter().all(|&m| m == Match::Correct));
+        let score = match won {
+            true => self.rows.len().to_string(),
+            false => "X".to_owned(),
+        };
+        format!("#{} {}/{}", self.number, score, TRIES)
+    }
+
+    // The grid's tiles, centred, and mirrored for right-to-left games.
+    fn tiles(&self) -> Vec<CardTile> {
+        let columns = self.rows.first().map_or(0, Vec::len);
+        let left = (CARD_WIDTH - columns * (CARD_TILE + CARD_GAP) + CARD_GAP) / 2;
+        let mut tiles = vec![];
+        for (i, row) in self.rows.iter().enumerate() {
+            for (j, state) in row.iter().enumerate() {
+                let column = match self.game.direction {
+                    Direction::Ltr => j,
+                    Direction::Rtl => columns - 1 - j,
+                };
+                tiles.push(CardTile {
+                    x: left + column * (CARD_TILE + CARD_GAP),
+                    y: CARD_GRID_TOP + i * (CARD_TILE + CARD_GAP),
+                    fill: tile_fill(*state, self.contrast),
+                    letter: None,
+                });
+            }
+        }
+        tiles
+    }
+}
+
+impl ResultPage<'_> {
+    // e.g. "Someone solved Fastly POPs #42 in 3/6!"
+    fn heading(&self) -> String {
+        let won = self
+            .rows
+            .last()
+            .is_some_and(|row| row.iter().all(|&m| m == Match::Correct));
+        match won {
+            true => i18n::fill(
+                self.t.solved,
+                [
+                    self.game.game.to_owned(),
+                    self.number.to_string(),
+                    format!("{}/{}", self.rows.len(), TRIES),
+                ],
+            ),
+            false => i18n::fill(
+                self.t.not_solved,
+                [self.game.game.to_owned(), self.number.to_string()],
+            ),
+        }
+    }
+
+    // The board, with the state of each tile but no letters.
+    fn board(&self) -> Vec<Row> {
+        self.rows
+            .iter()
+            .map(|row| Row {
+                tiles: row
+                    .iter()
+                    .map(|state| Tile {
+                        letter: String::new(),
+                        state: state.as_str(),
+                        label: state.description(self.t).to_owned(),
+                    })
+                    .collect(),
+                active: false,
+            })
+            .collect()
+    }
+}
+
+impl GameCard<'_> {
+    // The letters of the game's name on tiles, centred, and in the order they're read.
+    fn tiles(&self) -> Vec<CardTile> {
+        let letters: Vec<char> = self.game.game.chars().collect();
+        let left =
+            (CARD_WIDTH + CARD_GAP).saturating_sub(letters.len() * (COVER_TILE + CARD_GAP)) / 2;
+        let states = [Match::Correct, Match::Near, Match::Wrong];
+        letters
+            .iter()
+            .enumerate()
+            .filter(|(_, letter)| !letter.is_whitespace())
+            .map(|(i, letter)| {
+                let column = match self.game.direction {
+                    Direction::Ltr => i,
+                    Direction::Rtl => letters.len() - 1 - i,
+                };
+                CardTile {
+                    x: left + column * (COVER_TILE + CARD_GAP),
+                    y: COVER_TOP,
+                    fill: tile_fill(states[i % states.len()], false),
+                    letter: Some(*letter),
+                }
+            })
+            .collect()
+    }
+
+    // The description, split into lines that fit on the card.
+    fn description(&self) -> Vec<String> {
+        let mut lines: Vec<String> = vec![];
+        for word in self.game.description.split_whitespace() {
+            match lines.last_mut() {
+                Some(line) if line.chars().count() + 1 + word.chars().count() <= COVER_LINE => {
+                    line.push(' ');
+                    line.push_str(word);
+                }
+                _ => lines.push(word.to_owned()),
+            }
+        }
+        lines
+    }
+}
+
+impl GamePage<'_> {
+    // The rows of the game's on-screen keyboard, with the best state of each letter guessed today.
+    fn keyboard(&self) -> Vec<KeyboardRow> {
+        let states = self.guesses.letter_states();
+        self.game
+            .keyboard()
+            .into_iter()
+            .map(|row| KeyboardRow {
+                keys: row
+                    .letters
+                    .iter()
+                    .map(|letter| {
+                        let upper = letter.to_uppercase().to_string();
+                        let state = states.get(&upper);
+                        Tile {
+                            letter: letter.to_string(),
+                            state: state.map_or("", Match::as_str),
+                            label: state.map_or(String::new(), |state| {
+                                format!("{}, {}", upper, state.description(self.t))
+                            }),
+                        }
+                    })
+                    .collect(),
+                spacers: row.spacers,
+                actions: row.actions,
+            })
+            .collect()
+    }
+
+    // Today's guesses, followed by the rows left to guess.
+    fn rows(&self) -> Vec<Row> {
+        (0..TRIES)
+            .map(|i| match self.guesses.outcome.get(i) {
+                Some(guess) => Row {
+                    tiles: guess
+                        .0
+                        .chars()
+                        .zip(guess.1.iter())
+                        .zip(guess.labels(self.t))
+                        .map(|((letter, state), label)| Tile {
+                            letter: letter.to_string(),
+                            state: state.as_str(),
+                            label,
+                        })
+                        .collect(),
+                    active: false,
+                },
+                None => Row {
+                    tiles: (0..self.guesses.today_length)
+                        .map(|_| Tile {
+                            letter: String::new(),
+                            state: "",
+                            label: String::new(),
+                        })
+                        .collect(),
+                    active: i == self.guesses.outcome.len() && !self.guesses.is_over(),
+                },
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,149 @@
+use fastly::http::{header, Method, StatusCode};
+use fastly::{Error, Request, Response};
+use std::collections::HashMap;
+
+// Path parameters, e.g. `slug` in `/{slug}/stats`.
+pub type Params = HashMap<String, String>;
+pub type Handler = fn(&mut Request, &Params) -> Result<Response, Error>;
+
+struct Route {
+    method: Method,
+    pattern: &'static str,
+    handler: Handler,
+}
+
+pub struct Router {
+    routes: Vec<Route>,
+    fallback: Handler,
+}
+
+impl Router {
+    // Initialize a router, with a handler for requests that don't match any route.
+    pub fn new(fallback: Handler) -> Self {
+        Router {
+            routes: Vec::new(),
+            fallback,
+        }
+    }
+
+    // Register a handler for a method and path pattern. Patterns are matched in order.
+    pub fn route(mut self, method: Method, pattern: &'static str, handler: Handler) -> Self {
+        self.routes.push(Route {
+            method,
+            pattern,
+            handler,
+        });
+        self
+    }
+
+    pub fn get(self, pattern: &'static str, handler: Handler) -> Self {
+        self.route(Method::GET, pattern, handler)
+    }
+
+    pub fn post(self, pattern: &'static str, handler: Handler) -> Self {
+        self.route(Method::POST, pattern, handler)
+    }
+
+    // Route a request to the handler for the first pattern that matches its path.
+    pub fn handle(&self, mut req: Request) -> Result<Response, Error> {
+        let path = req.get_path().to_owned();
+        let (pattern, params) =
+            match self.routes.iter().find_map(|route| {
+                matches(route.pattern, &path).map(|params| (route.pattern, params))
+            }) {
+                Some(found) => found,
+                None => return (self.fallback)(&mut req, &Params::new()),
+            };
+        let routes: Vec<&Route> = self
+            .routes
+            .iter()
+            .filter(|route| route.pattern == pattern)
+            .collect();
+        let method = req.get_method().clone();
+        if let Some(route) = routes.iter().find(|route| route.method == method) {
+            return (route.handler)(&mut req, &params);
+        }
+        // HEAD is derived from GET, without the body or any cookies. Handlers with side effects
+        // should check for HEAD requests and respond early.
+        if method == Method::HEAD {
+            if let Some(route) = routes.iter().find(|route| route.method == Method::GET) {
+                let mut resp = (route.handler)(&mut req, &params)?;
+                resp.take_body();
+                resp.remove_header(header::SET_COOKIE);
+                return Ok(resp);
+            }
+        }
+        // OPTIONS (including CORS preflight requests) lists the methods allowed for the route.
+        if method == Method::OPTIONS {
+            return Ok(Response::from_status(StatusCode::NO_CONTENT)
+                .with_header(header::ALLOW, allow(&routes)));
+        }
+        Ok(Response::from_status(StatusCode::METHOD_NOT_ALLOWED)
+            .with_header(header::ALLOW, allow(&routes)))
+    }
+}
+
+// The methods allowed for a path pattern.
+fn allow(routes: &[&Route]) -> String {
+    let mut methods: Vec<&str> = routes.iter().map(|route| route.method.as_str()).collect();
+    if methods.contains(&"GET") && !methods.contains(&"HEAD") {
+        methods.push("HEAD");
+    }
+    if !methods.contains(&"OPTIONS") {
+        methods.push("OPTIONS");
+    }
+    methods.join(", ")
+}
+
+// Match a path against a pattern, capturing `{name}` segments as parameters.
+fn matches(pattern: &str, path: &str) -> Option<Params> {
+    let pattern_segments: Vec<&str> = pattern.split('/').collect();
+    let path_segments: Vec<&str> = path.split('/').collect();
+    if pattern_segments.len() != path_segments.len() {
+        return None;
+    }
+    let mut params = Params::new();
+    for (pattern_segment, path_segment) in pattern_segments.iter().zip(path_segments) {
+        if pattern_segment.starts_with('{') && pattern_segment.ends_with('}') {
+            if path_segment.is_empty() {
+                return None;
+            }
+            let name = &pattern_segment[1..pattern_segment.len() - 1];
+            params.insert(name.to_owned(), path_segment.to_owned());
+        } else if *pattern_segment != path_segment {
+            return None;
+        }
+    }
+    Some(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_static_paths() {
+        assert_eq!(matches("/new", "/new"), Some(Params::new()));
+        assert_eq!(matches("/new", "/newer"), None);
+        assert_eq!(matches("/", "/"), Some(Params::new()));
+    }
+
+    #[test]
+    fn matches_captures_parameters() {
+        let params = matches("/{slug}/result/{token}", "/pops/result/42.ccccc.0.abc").unwrap();
+        assert_eq!(params["slug"], "pops");
+        assert_eq!(params["token"], "42.ccccc.0.abc");
+        assert_eq!(
+            matches("/{slug}/embed", "/pops/embed").unwrap()["slug"],
+            "pops"
+        );
+    }
+
+    #[test]
+    fn matches_needs_every_segment() {
+        assert_eq!(matches("/{slug}", "/"), None);
+        assert_eq!(matches("/{slug}", "/pops/embed"), None);
+        assert_eq!(matches("/{slug}/embed", "/pops/stats"), None);
+        assert_eq!(matches("/{slug}/embed", "/pops/embed/"), None);
+    }
+}
@@ -0,0 +1,73 @@
+use fastly::http::header;
+use fastly::{mime, Response};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+const HSTS: &str = "max-age=31536000";
+const REFERRER_POLICY: &str = "strict-origin-when-cross-origin";
+
+// Each Compute@Edge instance handles a single request, so this is a per-request nonce.
+static NONCE: OnceLock<String> = OnceLock::new();
+
+// The CSP nonce for the current request, added to every script tag in the templates.
+pub fn nonce() -> &'static str {
+    NONCE.get_or_init(|| Uuid::new_v4().simple().to_string())
+}
+
+// The Content-Security-Policy directives. Scripts need the nonce, and can load others (e.g.
+// Google Analytics) from there.
+fn policy() -> Vec<(&'static str, String)> {
+    vec![
+        ("default-src", "'self'".to_owned()),
+        ("script-src", format!("'nonce-{}' 'strict-dynamic'", nonce())),
+        ("style-src", "'self' https://fonts.googleapis.com".to_owned()),
+        ("font-src", "https://fonts.gstatic.com".to_owned()),
+        (
+            "img-src",
+            "'self' https://*.google-analytics.com https://*.googletagmanager.com".to_owned(),
+        ),
+        (
+            "connect-src",
+            "'self' https://*.google-analytics.com https://*.analytics.google.com https://*.googletagmanager.com".to_owned(),
+        ),
+        ("object-src", "'none'".to_owned()),
+        ("base-uri", "'none'".to_owned()),
+        ("form-action", "'self'".to_owned()),
+        ("frame-ancestors", "'self'".to_owned()),
+    ]
+}
+
+// Add the security headers to a response, on every route. Handlers can set their own CSP
+// directives (e.g. `frame-ancestors` for embeds), which take precedence over the defaults.
+pub fn finalize(mut resp: Response) -> Response {
+    let own = resp
+        .get_header_str(header::CONTENT_SECURITY_POLICY)
+        .unwrap_or_default()
+        .to_owned();
+    let mut directives: Vec<String> = own
+        .split(';')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(String::from)
+        .collect();
+    for (name, value) in policy() {
+        if !directives
+            .iter()
+            .any(|directive| directive.split_whitespace().next() == Some(name))
+        {
+            directives.push(format!("{} {}", name, value));
+        }
+    }
+    resp.set_header(header::CONTENT_SECURITY_POLICY, directives.join("; "));
+    resp.set_header(header::STRICT_TRANSPORT_SECURITY, HSTS);
+    resp.set_header(header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+    resp.set_header(header::REFERRER_POLICY, REFERRER_POLICY);
+    // Static assets are precompressed, but rendered HTML is compressed by Fastly on the way out.
+    if resp
+        .get_content_type()
+        .is_some_and(|mime| mime.essence_str() == mime::TEXT_HTML.essence_str())
+    {
+        resp.set_header("x-compress-hint", "on");
+    }
+    resp
+}
@@ -1,16 +1,39 @@
+use crate::guess::{Guesses, Progress};
+use crate::{config, token};
+use fastly::http::header;
+use fastly::{Request, Response};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 const COOKIE_NAME: &str = "yourdle";
 const MAX_AGE: u64 = 365 * 24 * 60 * 60 * 1000; // 1 year
+const PROGRESS_MAX_AGE: u64 = 2 * 24 * 60 * 60; // 2 days
 
-pub fn get_user_id(cookie_string: &str) -> String {
-    match cookie_string
+// Cross-origin clients can't use cookies, so today's progress is also handed over in a header.
+pub const PROGRESS_HEADER: &str = "x-progress-token";
+
+// A signed player identity, for cross-origin clients that can't use the cookie.
+#[derive(Serialize, Deserialize)]
+struct Identity {
+    #[serde(rename = "id")]
+    user_id: String,
+}
+
+// Returns the value of a cookie, if set.
+fn get_cookie<'a>(cookie_string: &'a str, name: &str) -> Option<&'a str> {
+    cookie_string
         .split("; ")
-        .find(|c| c.starts_with(&format!("{}=", COOKIE_NAME)))
-    {
-        Some(c) => c[(&COOKIE_NAME.len() + 1)..].to_string(),
-        None => Uuid::new_v4().to_string(),
-    }
+        .find(|c| c.starts_with(&format!("{}=", name)))
+        .map(|c| &c[(name.len() + 1)..])
+}
+
+// Returns the user ID from the cookie, if set.
+pub fn find_user_id(cookie_string: &str) -> Option<String> {
+    get_cookie(cookie_string, COOKIE_NAME).map(String::from)
+}
+
+pub fn get_user_id(cookie_string: &str) -> String {
+    find_user_id(cookie_string).unwrap_or_else(|| Uuid::new_v4().to_string())
 }
 
 pub fn set_user_id(id: &str) -> String {
@@ -19,3 +42,68 @@ pub fn set_user_id(id: &str) -> String {
         COOKIE_NAME, id, MAX_AGE
     )
 }
+
+// Embedded games are in a third-party context, where only partitioned cookies are sent.
+pub fn set_embedded_user_id(id: &str) -> String {
+    format!(
+        "{}={}; Max-Age={}; Path=/; SameSite=None; Secure; Partitioned",
+        COOKIE_NAME, id, MAX_AGE
+    )
+}
+
+// Issue an identity token for a new player.
+pub fn issue_identity() -> Result<String, &'static str> {
+    token::sign(&Identity {
+        user_id: Uuid::new_v4().to_string(),
+    })
+}
+
+// Returns the user ID from an `Authorization: Bearer` identity token, if valid.
+pub fn find_bearer_id(req: &Request) -> Option<String> {
+    let token = req
+        .get_header_str(header::AUTHORIZATION)?
+        .strip_prefix("Bearer ")?;
+    token::verify::<Identity>(token.trim())
+        .ok()
+        .map(|identity| identity.user_id)
+}
+
+// Returns the signed token holding today's progress in a game (stateless mode).
+fn get_progress(cookie_string: &str, game: &str) -> Option<String> {
+    get_cookie(cookie_string, &format!("{}-{}", COOKIE_NAME, game)).map(String::from)
+}
+
+fn set_progress(game: &str, token: &str) -> String {
+    format!(
+        "{}-{}={}; Max-Age={}; Path=/; HttpOnly; SameSite=Lax; Secure",
+        COOKIE_NAME, game, token, PROGRESS_MAX_AGE
+    )
+}
+
+// Load a player's guesses. In stateless mode, today's progress is carried in a signed cookie
+// (or header).
+pub fn load_guesses(req: &Request, game: &str, user_id: &str, today_word_length: usize) -> Guesses {
+    let cookie_string = req.get_header_str("cookie").unwrap_or_default();
+    let progress = match config::stateless() {
+        true => get_progress(cookie_string, game)
+            .or_else(|| req.get_header_str(PROGRESS_HEADER).map(String::from))
+            .and_then(|token| token::verify::<Progress>(&token).ok()),
+        false => None,
+    };
+    Guesses::load(game, user_id, today_word_length, progress)
+}
+
+// Hand today's progress back to the player in a signed cookie and header (stateless mode only).
+pub fn save_progress(
+    resp: &mut Response,
+    guesses: &Guesses,
+    game: &str,
+    user_id: &str,
+) -> Result<(), &'static str> {
+    if config::stateless() {
+        let progress = token::sign(&guesses.progress(game, user_id))?;
+        resp.append_header(header::SET_COOKIE, set_progress(game, &progress));
+        resp.set_header(PROGRESS_HEADER, progress);
+    }
+    Ok(())
+}
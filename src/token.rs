@@ -0,0 +1,79 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use fastly::SecretStore;
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Sha256;
+
+const SECRET_STORE_NAME: &str = "yourdle-secrets";
+const SIGNING_KEY: &str = "signing-key";
+// Short signatures are truncated to 16 bytes, which is still plenty for links.
+const SHORT_SIGNATURE_LEN: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Initialize a MAC with the signing key from the secret store.
+fn mac() -> Result<HmacSha256, &'static str> {
+    let key = match SecretStore::open(SECRET_STORE_NAME) {
+        Ok(secret_store) => match secret_store.get(SIGNING_KEY) {
+            Some(secret) => secret.plaintext(),
+            None => return Err("Could not load signing key"),
+        },
+        _ => return Err("Could not open secret store"),
+    };
+    HmacSha256::new_from_slice(&key).map_err(|_| "Invalid signing key")
+}
+
+// Serialize a payload into a compact, signed `payload.signature` token.
+pub fn sign<T: Serialize>(payload: &T) -> Result<String, &'static str> {
+    let payload = match serde_json::to_vec(payload) {
+        Ok(json) => URL_SAFE_NO_PAD.encode(json),
+        _ => return Err("Could not serialize token payload"),
+    };
+    let mut mac = mac()?;
+    mac.update(payload.as_bytes());
+    Ok(format!(
+        "{}.{}",
+        payload,
+        URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+    ))
+}
+
+// Verify a token's signature and deserialize its payload.
+pub fn verify<T: DeserializeOwned>(token: &str) -> Result<T, &'static str> {
+    let (payload, signature) = token.split_once('.').ok_or("Malformed token")?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| "Malformed token")?;
+    let mut mac = mac()?;
+    mac.update(payload.as_bytes());
+    if mac.verify_slice(&signature).is_err() {
+        return Err("Invalid token signature");
+    }
+    match URL_SAFE_NO_PAD.decode(payload) {
+        Ok(json) => serde_json::from_slice(&json).map_err(|_| "Invalid token payload"),
+        _ => Err("Malformed token"),
+    }
+}
+
+// Sign a short message, for tokens that need to fit in a link: the signature is truncated and
+// only covers the message with its `purpose`, so it can't be passed off as another kind of token.
+pub fn sign_short(purpose: &str, message: &str) -> Result<String, &'static str> {
+    let mut mac = mac()?;
+    mac.update(format!("{}:{}", purpose, message).as_bytes());
+    Ok(URL_SAFE_NO_PAD.encode(&mac.finalize().into_bytes()[..SHORT_SIGNATURE_LEN]))
+}
+
+// Verify the signature of a short message.
+pub fn verify_short(purpose: &str, message: &str, signature: &str) -> Result<(), &'static str> {
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| "Malformed token")?;
+    if signature.len() != SHORT_SIGNATURE_LEN {
+        return Err("Malformed token");
+    }
+    let mut mac = mac()?;
+    mac.update(format!("{}:{}", purpose, message).as_bytes());
+    mac.verify_truncated_left(&signature)
+        .map_err(|_| "Invalid token signature")
+}
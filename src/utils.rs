@@ -1,4 +1,6 @@
 use chrono::{TimeZone, Utc};
+use fastly::http::Url;
+use fastly::Request;
 use std::collections::HashSet;
 
 // Returns the current timestamp.
@@ -19,12 +21,11 @@ pub fn date_iso8601() -> String {
     Utc::now().to_rfc3339()[..10].to_string()
 }
 
-// Truncates a string to a maximum length.
+// Truncates a string to a maximum number of characters.
 pub fn truncate_to_chars(s: &str, max_len: usize) -> &str {
-    if s.len() > max_len {
-        &s[..max_len]
-    } else {
-        s
+    match s.char_indices().nth(max_len) {
+        Some((end, _)) => &s[..end],
+        None => s,
     }
 }
 
@@ -34,14 +35,14 @@ pub fn sanitize_as_words(text: String) -> Vec<String> {
     let unique_words: HashSet<String> = text
         // Replace whitespace and punctuation with a single space.
         .replace(|c: char| c.is_whitespace() || c.is_ascii_punctuation(), " ")
-        // Remove non-ASCII alphabet characters.
-        .replace(|c: char| !c.is_whitespace() && !c.is_ascii_alphabetic(), "")
+        // Remove anything but letters, in any alphabet.
+        .replace(|c: char| !c.is_whitespace() && !c.is_alphabetic(), "")
         .to_uppercase()
         .trim()
         // Split into words...
         .split_whitespace()
         // ...that are at least 3 and at most 10 letters long.
-        .filter(|&s| s.len() >= 3 && s.len() <= 10)
+        .filter(|&s| (3..=10).contains(&s.chars().count()))
         .map(String::from)
         // Maximum 365 words.
         .take(365)
@@ -49,3 +50,31 @@ pub fn sanitize_as_words(text: String) -> Vec<String> {
 
     unique_words.into_iter().collect()
 }
+
+// Takes a list of URLs separated by whitespace or commas and returns their unique web origins.
+pub fn sanitize_as_origins(text: &str) -> Vec<String> {
+    let mut origins: Vec<String> = Vec::new();
+    for url in text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|s| Url::parse(s).ok())
+        .filter(|url| url.scheme() == "https" || url.scheme() == "http")
+    {
+        let origin = url.origin().ascii_serialization();
+        if !origins.contains(&origin) {
+            origins.push(origin);
+        }
+    }
+    // Maximum 10 origins.
+    origins.truncate(10);
+    origins
+}
+
+// Returns the URL of a game, on the host the request was made to.
+pub fn game_url(req: &Request, slug: &str) -> String {
+    format!("{}/{}", req.get_url().origin().ascii_serialization(), slug)
+}
+
+// Returns the number of seconds until the next day starts.
+pub fn secs_until_tomorrow() -> i64 {
+    86400 - timestamp_now().rem_euclid(86400)
+}